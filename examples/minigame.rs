@@ -1,11 +1,11 @@
-use carose::{Window, TextAlign, Menu};
+use carose::{Window, TextAlign, Menu, Layer};
 use carose::colors::{WHITE, RED, BLACK};
-use carose::sprites::{SpriteType, SpriteRender, Sprite, Vector};
+use carose::sprites::{SpriteType, SpriteRender, Sprite, SpriteId, Vector};
 use carose::audio::{Audio, Bgs, SoundSource, BuiltInSound};
-use carose::controls::Key;
+use carose::controls::{ActionMap, Binding, GamepadAxis, GamepadButton, Key};
 use rand::Rng;
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 fn remove_menu_text(window: &mut Window, menu: &Menu, prefix: &str) {
     for i in 0..menu.options.len() {
@@ -13,28 +13,50 @@ fn remove_menu_text(window: &mut Window, menu: &Menu, prefix: &str) {
     }
 }
 
-fn handle_menu_input(window: &mut Window, menu: &mut Menu) {
-    if window.controls.clicked(Key::Char('w')) | window.controls.clicked(Key::Up) {
+fn default_actions() -> ActionMap {
+    let mut actions = ActionMap::new();
+    for key in [Key::Char('w'), Key::Up] {
+        actions.bind("up", Binding::Key(key));
+    }
+    actions.bind("up", Binding::Button(GamepadButton::DPadUp));
+    for key in [Key::Char('s'), Key::Down] {
+        actions.bind("down", Binding::Key(key));
+    }
+    actions.bind("down", Binding::Button(GamepadButton::DPadDown));
+    actions.bind("confirm", Binding::Key(Key::Enter));
+    actions.bind("confirm", Binding::Button(GamepadButton::South));
+    actions.bind("pause", Binding::Key(Key::Escape));
+    actions.bind("pause", Binding::Button(GamepadButton::Start));
+    actions.bind("fire", Binding::Key(Key::Space));
+    actions.bind("fire", Binding::Button(GamepadButton::South));
+    actions.bind_axis("move_x", Binding::Key(Key::Char('a')), Binding::Key(Key::Char('d')));
+    actions.bind_axis("move_x", Binding::AxisNegative(GamepadAxis::LeftX), Binding::AxisPositive(GamepadAxis::LeftX));
+    actions
+}
+
+fn handle_menu_input(actions: &ActionMap, menu: &mut Menu) {
+    if actions.repeated("up") {
         menu.move_up();
     }
-    if window.controls.clicked(Key::Char('s')) | window.controls.clicked(Key::Down) {
+    if actions.repeated("down") {
         menu.move_down();
     }
 }
 
-fn main_menu(window: &mut Window, bgs: &mut Bgs) -> usize {
+fn main_menu(window: &mut Window, actions: &mut ActionMap, bgs: &mut Bgs) -> SpriteId {
     let mut menu = Menu::new(vec!["Play", "Exit"], RED, BLACK);
     window.set_background_color(WHITE);
     let player_index;
 
     loop {
         window.update_controls();
+        actions.update(&window.controls);
         let (width, height) = window.get_size();
 
         menu.draw(window, "main_option");
-        handle_menu_input(window, &mut menu);
+        handle_menu_input(actions, &mut menu);
 
-        if window.controls.clicked(Key::Enter) {
+        if actions.just_pressed("confirm") {
             match menu.current() {
                 "Play" => {
                     let frames = (1..=3)
@@ -42,7 +64,7 @@ fn main_menu(window: &mut Window, bgs: &mut Bgs) -> usize {
                         .collect::<Vec<_>>();
 
                     player_index = window.create_animated_bitmap_sprite_from_files(
-                        ((width / 2).saturating_sub(64 / 2), height - 100),
+                        ((width / 2).saturating_sub(64 / 2) as f32, (height - 100) as f32),
                         100,
                         frames,
                         SpriteType::Player,
@@ -66,6 +88,7 @@ fn main_menu(window: &mut Window, bgs: &mut Bgs) -> usize {
 
 // --- Game Functions ---
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_falling(
     window: &mut Window,
     rng: &mut impl Rng,
@@ -74,17 +97,20 @@ fn spawn_falling(
     size: (usize, usize),
     color: u32,
     health: i32,
-    speed: i32,
+    speed: f32,
 ) {
     let x = rng.random_range(50..(width - 50));
-    window.sprites.push(Sprite {
+    window.add_sprite(Sprite {
         sprite_type,
         health,
-        position: (x, 0),
+        position: (x as f32, 0.0),
         size,
         render: SpriteRender::Color(color),
-        vectors: vec![Vector::Velocity(0, speed)],
+        vectors: vec![Vector::Velocity(0.0, speed)],
         is_solid: false,
+        opacity: 255,
+        layer: Layer::WORLD,
+        z_index: 0,
     });
 }
 
@@ -106,17 +132,20 @@ fn main() {
 
     let mut window = Window::new("Arc Shooter", 800, 600);
     window.set_background_color(BLACK);
+    window.enable_gamepads();
+    let mut actions = default_actions();
+    let _ = actions.load("bindings.cfg");
     let audio = Audio::new();
     let mut rng = rand::rng();
 
-    let mut player_index = main_menu(&mut window, &mut bgs);
+    let mut player_index = main_menu(&mut window, &mut actions, &mut bgs);
 
     let mut projectiles = Vec::new();
     let mut score = 0;
     let mut spawn_rate: f32 = rng.random_range(1.3..1.5);
     let food_spawn_rate: f32 = 15.0;
-    let mut last_spawn = Instant::now();
-    let mut last_food_spawn = Instant::now();
+    let mut last_spawn = window.clock.elapsed();
+    let mut last_food_spawn = window.clock.elapsed();
     let score_id = "score";
     let health_id = "health";
     window.show_text(score_id, &format!("Score: {}", score), (10, 10), 4, WHITE, TextAlign::AutoFit);
@@ -129,23 +158,24 @@ fn main() {
     while window.is_open() {
         bgs.update_playlist();
         window.update_controls();
+        actions.update(&window.controls);
         let (width, _) = window.get_size();
 
         if !window.is_focused() { paused = true; }
-        if window.controls.clicked(Key::Escape) { paused = true; }
+        if actions.just_pressed("pause") { paused = true; }
 
         // --- Pause Menu ---
         if paused {
             pause_menu.draw(&mut window, "pause_option");
-            handle_menu_input(&mut window, &mut pause_menu);
+            handle_menu_input(&actions, &mut pause_menu);
 
-            if window.controls.clicked(Key::Enter) {
+            if actions.just_pressed("confirm") {
                 match pause_menu.current() {
                     "Resume" => { paused = false; remove_menu_text(&mut window, &pause_menu, "pause_option"); }
                     "Exit" => {
                         remove_menu_text(&mut window, &pause_menu, "pause_option");
                         window.sprites.clear();
-                        player_index = main_menu(&mut window, &mut bgs);
+                        player_index = main_menu(&mut window, &mut actions, &mut bgs);
                         player_dead = false;
                         paused = false;
                     }
@@ -157,26 +187,28 @@ fn main() {
         }
 
         // --- Update HUD ---
-        let health = if player_dead { 0 } else { window.sprites[player_index].health };
+        let health = if player_dead { 0 } else { window.sprite(player_index).map_or(0, |p| p.health) };
         window.update_text(score_id, &format!("Score: {}", score));
         window.update_text(health_id, &format!("Health: {}", health));
 
         // --- Player Actions ---
         if !player_dead {
             let mut pos = window.sprites[player_index].position;
-            if window.controls.pressed(Key::Char('a')) { pos.0 = pos.0.saturating_sub(10); }
-            if window.controls.pressed(Key::Char('d')) { pos.0 = (pos.0 + 10).min(width - window.sprites[player_index].size.0); }
+            pos.0 += actions.value("move_x") * 10.0;
+            pos.0 = pos.0.clamp(0.0, (width - window.sprites[player_index].size.0) as f32);
 
-            if window.controls.clicked(Key::Space) {
-                let idx = window.sprites.len();
-                window.sprites.push(Sprite {
+            if actions.just_pressed("fire") {
+                let idx = window.add_sprite(Sprite {
                     sprite_type: SpriteType::Projectile,
                     health: 1,
-                    position: (pos.0 + 20, pos.1),
+                    position: (pos.0 + 20.0, pos.1),
                     size: (10, 10),
                     render: SpriteRender::Color(WHITE),
                     is_solid: false,
-                    vectors: vec![Vector::Velocity(0, -10)],
+                    opacity: 255,
+                    layer: Layer::WORLD,
+                    z_index: 0,
+                    vectors: vec![Vector::Velocity(0.0, -10.0)],
                 });
                 projectiles.push(idx);
                 audio.play(SoundSource::BuiltIn(BuiltInSound::Shoot));
@@ -185,17 +217,17 @@ fn main() {
         }
 
         // --- Enemy Spawn ---
-        if last_spawn.elapsed().as_secs_f32() > spawn_rate {
+        if (window.clock.elapsed() - last_spawn).as_secs_f32() > spawn_rate {
             spawn_rate = rng.random_range(1.0..1.2);
-            let speed = rng.random_range(7..13);
+            let speed = rng.random_range(7.0..13.0);
             spawn_falling(&mut window, &mut rng, width, SpriteType::Enemy, (50, 50), RED, 30, speed);
-            last_spawn = Instant::now();
+            last_spawn = window.clock.elapsed();
         }
 
-        if last_food_spawn.elapsed().as_secs_f32() > food_spawn_rate {
+        if (window.clock.elapsed() - last_food_spawn).as_secs_f32() > food_spawn_rate {
             spawn_rate = rng.random_range(1.0..1.2);
-            spawn_falling(&mut window, &mut rng, width, SpriteType::Custom("Food"), (30, 30), 0x00FF00, 1, 10);
-            last_food_spawn = Instant::now();
+            spawn_falling(&mut window, &mut rng, width, SpriteType::Custom("Food"), (30, 30), 0x00FF00, 1, 10.0);
+            last_food_spawn = window.clock.elapsed();
         }
         window.change_health_on_collision(SpriteType::Player, SpriteType::Custom("Food"), 20);
         window.remove_on_collision(SpriteType::Player, SpriteType::Custom("Food"));
@@ -212,13 +244,13 @@ fn main() {
         window.remove_on_death(SpriteType::Projectile);
         window.remove_on_death(SpriteType::Enemy);
         window.prevent_leaving_screen(SpriteType::Player);
-        window.add_vector(SpriteType::Projectile, Vector::Velocity(0, -10));
+        window.add_vector(SpriteType::Projectile, Vector::Velocity(0.0, -10.0));
         window.apply_vectors();
 
         window.draw();
 
         // --- Player Death Check ---
-        if !player_dead && window.sprites[player_index].health == 0 {
+        if !player_dead && window.sprite(player_index).is_some_and(|p| p.health == 0) {
             window.remove_sprite(player_index);
             window.update_text(health_id, &format!("Health: {}", health));
 
@@ -226,10 +258,11 @@ fn main() {
             let mut gameover_menu = Menu::new(vec!["Play Again", "Exit"], RED, WHITE);
             loop {
                 window.update_controls();
+                actions.update(&window.controls);
                 gameover_menu.draw(&mut window, "gameover_option");
-                handle_menu_input(&mut window, &mut gameover_menu);
+                handle_menu_input(&actions, &mut gameover_menu);
 
-                if window.controls.clicked(Key::Enter) {
+                if actions.just_pressed("confirm") {
                     match gameover_menu.current() {
                         "Play Again" => {
                             window.sprites.clear();
@@ -241,7 +274,7 @@ fn main() {
                                 .map(|i| format!("assets/Sprites/Animated/Ship/shipsprite{}.bmp", i))
                                 .collect::<Vec<_>>();
                             player_index = window.create_animated_bitmap_sprite_from_files(
-                                (375, 500), 100, frames, SpriteType::Player, 120
+                                (375.0, 500.0), 100, frames, SpriteType::Player, 120
                             );
 
                             remove_menu_text(&mut window, &gameover_menu, "gameover_option");
@@ -1,12 +1,12 @@
 use carose::{
-    Window, Menu, TextAlign,
+    Window, Menu, TextAlign, Layer,
     colors::{WHITE, RED, BLACK},
-    sprites::{SpriteType, SpriteRender, Sprite, Vector},
-    controls::Key,
+    sprites::{SpriteType, SpriteRender, Sprite, SpriteId, Vector},
+    controls::{ActionMap, Binding, Key},
     audio::{Audio, Bgs, SoundSource, BuiltInSound},
 };
 use rand::Rng;
-use std::{thread, time::Duration, time::Instant, process};
+use std::{thread, time::Duration, process};
 
 fn main() {
     // --- Audio ---
@@ -18,33 +18,46 @@ fn main() {
     let mut window = Window::new("Flappy Bird Clone", 400, 600);
     window.set_background_color(BLACK);
 
+    // --- Controls ---
+    let mut actions = ActionMap::new();
+    actions.bind("up", Binding::Key(Key::Char('w')));
+    actions.bind("up", Binding::Key(Key::Up));
+    actions.bind("down", Binding::Key(Key::Char('s')));
+    actions.bind("down", Binding::Key(Key::Down));
+    actions.bind("confirm", Binding::Key(Key::Enter));
+    actions.bind("pause", Binding::Key(Key::Escape));
+    actions.bind("flap", Binding::Key(Key::Space));
+
     // --- Main Menu ---
     let mut menu = Menu::new(vec!["Play", "Exit"], RED, WHITE);
-    let mut player_index: usize;
+    let mut player_index: SpriteId;
     let mut rng = rand::rng();
 
     loop {
         window.update_controls();
+        actions.update(&window.controls);
         menu.draw(&mut window, "main_menu");
 
-        if window.controls.clicked(Key::Char('w')) || window.controls.clicked(Key::Up) { menu.move_up(); }
-        if window.controls.clicked(Key::Char('s')) || window.controls.clicked(Key::Down) { menu.move_down(); }
+        if actions.repeated("up") { menu.move_up(); }
+        if actions.repeated("down") { menu.move_down(); }
 
-        if window.controls.clicked(Key::Enter) {
+        if actions.just_pressed("confirm") {
             match menu.current() {
                 "Play" => {
                     remove_menu_text(&mut window, &menu, "main_menu");
 
                     // --- Create Player ---
-                    player_index = window.sprites.len();
-                    window.sprites.push(Sprite {
+                    player_index = window.add_sprite(Sprite {
                         sprite_type: SpriteType::Player,
                         health: 1,
-                        position: (100, 300),
+                        position: (100.0, 300.0),
                         size: (30, 30),
                         render: SpriteRender::Color(WHITE),
-                        vectors: vec![Vector::Velocity(0, 0)], // Gravity will pull down
+                        vectors: vec![Vector::Velocity(0.0, 0.0)], // Gravity will pull down
                         is_solid: false,
+                        opacity: 255,
+                        layer: Layer::WORLD,
+                        z_index: 0,
                     });
                     break;
                 }
@@ -58,7 +71,7 @@ fn main() {
 
     // --- Game Variables ---
     let mut score = 0;
-    let mut last_pipe_spawn = Instant::now();
+    let mut last_pipe_spawn = window.clock.elapsed();
     let pipe_interval = 2.0; // seconds
     let pipe_gap = 150;
     let mut paused = false;
@@ -69,31 +82,32 @@ fn main() {
     // --- Game Loop ---
     while window.is_open() {
         window.update_controls();
+        actions.update(&window.controls);
         window.update_text(score_id, &format!("Score: {}", score));
         let (width, height) = window.get_size();
         // --- Pause ---
-        if window.controls.clicked(Key::Escape) { paused = !paused; }
-        if window.controls.pressed(Key::LeftCtrl) && window.controls.clicked(Key::Char('c')) {
+        if actions.just_pressed("pause") { paused = !paused; }
+        if window.controls.pressed(Key::LeftCtrl) && window.controls.just_pressed(Key::Char('c')) {
             process::exit(0);
         }
         if paused { window.draw(); thread::sleep(Duration::from_millis(30)); continue; }
         score += 1;
         if !player_dead {
             // --- Flap ---
-            if window.controls.clicked(Key::Space) {
-                window.sprites[player_index].set_velocity(0, -12); // flap upward
+            if actions.just_pressed("flap") {
+                window.sprites[player_index].set_velocity(0.0, -12.0); // flap upward
                 audio.play(SoundSource::BuiltIn(BuiltInSound::Shoot));
             }
 
             // --- Gravity ---
             
-            window.sprites[player_index].set_acceleration(0, 1); // constant downward pull
+            window.sprites[player_index].set_acceleration(0.0, 1.0); // constant downward pull
         }
 
         // --- Spawn Pipes ---
-        if last_pipe_spawn.elapsed().as_secs_f32() > pipe_interval {
+        if (window.clock.elapsed() - last_pipe_spawn).as_secs_f32() > pipe_interval {
             spawn_pipe(&mut window, &mut rng, width, height, pipe_gap);
-            last_pipe_spawn = Instant::now();
+            last_pipe_spawn = window.clock.elapsed();
         }
 
         // --- Apply Vectors & Physics ---
@@ -113,10 +127,8 @@ fn main() {
         window.remove_if_out_of_screen(SpriteType::Custom("Pipe"));
 
         // --- Score Increment ---
-        for i in 0..window.sprites.len() {
-            if window.sprites[i].health <= 0 {
-                window.update_text(score_id, &format!("Score: {}", score));
-            }
+        if window.sprites.values().iter().any(|sprite| sprite.health <= 0) {
+            window.update_text(score_id, &format!("Score: {}", score));
         }
 
 
@@ -129,16 +141,18 @@ fn main() {
             window.sprites.clear();
             score = 0;
             player_dead = false;
-            last_pipe_spawn = Instant::now();
-            player_index = window.sprites.len();
-            window.sprites.push(Sprite {
+            last_pipe_spawn = window.clock.elapsed();
+            player_index = window.add_sprite(Sprite {
                 sprite_type: SpriteType::Player,
                 health: 1,
-                position: (100, 300),
+                position: (100.0, 300.0),
                 size: (30, 30),
                 render: SpriteRender::Color(WHITE),
-                vectors: vec![Vector::Velocity(0, 0)],
+                vectors: vec![Vector::Velocity(0.0, 0.0)],
                 is_solid: false,
+                opacity: 255,
+                layer: Layer::WORLD,
+                z_index: 0,
             });
         }
 
@@ -151,25 +165,31 @@ fn spawn_pipe(window: &mut Window, rng: &mut impl Rng, width: usize, height: usi
     let gap_y = rng.random_range(50..(height - gap - 50));
 
     // Top pipe
-    window.sprites.push(Sprite {
+    window.add_sprite(Sprite {
         sprite_type: SpriteType::Custom("Pipe"),
         health: 1,
-        position: (width, 0),
+        position: (width as f32, 0.0),
         size: (50, gap_y),
         render: SpriteRender::Color(0x00FF00),
-        vectors: vec![Vector::Velocity(-5, 0)],
+        vectors: vec![Vector::Velocity(-5.0, 0.0)],
         is_solid: false,
+        opacity: 255,
+        layer: Layer::WORLD,
+        z_index: 0,
     });
 
     // Bottom pipe
-    window.sprites.push(Sprite {
+    window.add_sprite(Sprite {
         sprite_type: SpriteType::Custom("Pipe"),
         health: 1,
-        position: (width, gap_y + gap),
+        position: (width as f32, (gap_y + gap) as f32),
         size: (50, height - gap_y - gap),
         render: SpriteRender::Color(0x00FF00),
-        vectors: vec![Vector::Velocity(-5, 0)],
+        vectors: vec![Vector::Velocity(-5.0, 0.0)],
         is_solid: false,
+        opacity: 255,
+        layer: Layer::WORLD,
+        z_index: 0,
     });
 }
 
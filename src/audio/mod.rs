@@ -16,6 +16,8 @@ pub enum SoundSource {
 pub enum BuiltInSound {
     Shoot,
     Kill,
+    /// Short, quiet tick used for typewriter text.
+    Blip,
 }
 
 /// Lightweight audio playback utility for one-shot sound effects.
@@ -25,6 +27,12 @@ pub enum BuiltInSound {
 /// manual lifecycle management.
 pub struct Audio {}
 
+impl Default for Audio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Audio {
     /// Creates a new audio playback helper.
     ///
@@ -59,7 +67,7 @@ impl Audio {
             // #[cfg(not(debug_assertions))]
             stream_handle.log_on_drop(false);
             // Create sink for playback
-            let sink = Sink::connect_new(&stream_handle.mixer());
+            let sink = Sink::connect_new(stream_handle.mixer());
 
             // Determine source and its duration
             let _duration: Duration = match sound {
@@ -71,14 +79,7 @@ impl Audio {
                     dur
                 }
                 SoundSource::BuiltIn(builtin) => {
-                    let source = match builtin {
-                        BuiltInSound::Shoot => SineWave::new(880.0)
-                            .take_duration(Duration::from_secs_f32(0.06))
-                            .amplify(0.25),
-                        BuiltInSound::Kill => SineWave::new(220.0)
-                            .take_duration(Duration::from_secs_f32(0.12))
-                            .amplify(0.25),
-                    };
+                    let source = builtin_source(&builtin);
                     let dur = source.total_duration().unwrap_or(Duration::from_secs_f32(0.25));
                     sink.append(source);
                     dur
@@ -91,6 +92,63 @@ impl Audio {
     }
 }
 
+/// Generates a built-in sound effect, played once.
+fn builtin_source(builtin: &BuiltInSound) -> impl Source<Item = f32> + Send + 'static {
+    match builtin {
+        BuiltInSound::Shoot => SineWave::new(880.0)
+            .take_duration(Duration::from_secs_f32(0.06))
+            .amplify(0.25),
+        BuiltInSound::Kill => SineWave::new(220.0)
+            .take_duration(Duration::from_secs_f32(0.12))
+            .amplify(0.25),
+        BuiltInSound::Blip => SineWave::new(660.0)
+            .take_duration(Duration::from_secs_f32(0.03))
+            .amplify(0.15),
+    }
+}
+
+/// Plays short sounds on one output stream that stays open.
+///
+/// Unlike [`Audio::play`], this needs no thread or stream per sound, so it
+/// suits sounds repeated many times a second, such as typewriter blips.
+/// Sounds overlap rather than queue. The stream is opened on the first
+/// sound; without an audio device, or for a file that cannot be decoded,
+/// sounds are skipped.
+pub(crate) struct EffectStream {
+    stream: Option<OutputStream>,
+    unavailable: bool,
+}
+
+impl EffectStream {
+    /// Creates a player that has not opened its stream yet.
+    pub(crate) fn new() -> Self {
+        Self { stream: None, unavailable: false }
+    }
+
+    /// Starts playing a sound over any that are still playing.
+    pub(crate) fn play(&mut self, sound: &SoundSource) {
+        if self.stream.is_none() && !self.unavailable {
+            match OutputStreamBuilder::open_default_stream() {
+                Ok(mut stream) => {
+                    stream.log_on_drop(false);
+                    self.stream = Some(stream);
+                }
+                Err(_) => self.unavailable = true,
+            }
+        }
+        let Some(stream) = &self.stream else { return };
+
+        match sound {
+            SoundSource::File(path) => {
+                if let Some(source) = File::open(path).ok().and_then(|file| Decoder::new(BufReader::new(file)).ok()) {
+                    stream.mixer().add(source);
+                }
+            }
+            SoundSource::BuiltIn(builtin) => stream.mixer().add(builtin_source(builtin)),
+        }
+    }
+}
+
 
 
 
@@ -127,7 +185,7 @@ impl Bgs {
     pub fn new(initial_source: SoundSource) -> Self {
         let _stream = OutputStreamBuilder::open_default_stream().expect("Failed stream");
         let stream_handle = OutputStreamBuilder::open_default_stream().expect("Failed handle");
-        let sink = Sink::connect_new(&stream_handle.mixer());
+        let sink = Sink::connect_new(stream_handle.mixer());
         let sink_arc = Arc::new(Mutex::new(sink));
 
         let bgs = Self {
@@ -165,7 +223,7 @@ impl Bgs {
         let _stream = OutputStreamBuilder::open_default_stream().expect("Failed stream");
         let stream_handle = OutputStreamBuilder::open_default_stream().expect("Failed handle");
 
-        let sink = Sink::connect_new(&stream_handle.mixer());
+        let sink = Sink::connect_new(stream_handle.mixer());
         let sink_arc = Arc::new(Mutex::new(sink));
         let first = sources[0].clone();
 
@@ -218,7 +276,7 @@ impl Bgs {
     ///
     /// # Parameters
     /// - `play`: If `true`, playback resumes or continues.
-    ///           If `false`, playback is paused.
+    ///   If `false`, playback is paused.
     ///
     /// # Notes
     /// - This does not reset the current sound.
@@ -256,7 +314,7 @@ impl Bgs {
         let mut sink_lock = self.sink.lock().unwrap();
         sink_lock.stop();
 
-        let new_sink = Sink::connect_new(&self.stream_handle.mixer());
+        let new_sink = Sink::connect_new(self.stream_handle.mixer());
 
         let rodio_source: Box<dyn Source<Item = f32> + Send> = match new_source {
             SoundSource::File(path) => {
@@ -278,6 +336,12 @@ impl Bgs {
                             .repeat_infinite()
                             .amplify(0.25),
                     ),
+                    BuiltInSound::Blip => Box::new(
+                        rodio::source::SineWave::new(660.0)
+                            .take_duration(Duration::from_secs_f32(0.03))
+                            .repeat_infinite()
+                            .amplify(0.15),
+                    ),
                 }
             }
         };
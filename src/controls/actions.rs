@@ -0,0 +1,719 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::{GamepadAxis, GamepadButton, Input, Key};
+
+/// Error returned when bindings cannot be loaded.
+#[derive(Debug)]
+pub enum BindingsError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+
+    /// A line of the file is malformed.
+    Parse {
+        /// 1-based line number.
+        line: usize,
+
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingsError::Io(err) => write!(f, "failed to access bindings file: {}", err),
+            BindingsError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for BindingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BindingsError::Io(err) => Some(err),
+            BindingsError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for BindingsError {
+    fn from(err: std::io::Error) -> Self {
+        BindingsError::Io(err)
+    }
+}
+
+/// A physical input that can drive an action.
+///
+/// Bindings have a text form used by [`ActionMap::save`] and
+/// [`ActionMap::load`]:
+/// - Keys by name: `a`, `7`, `F5`, `Space`, `Enter`, `Up`, `MouseLeft`, ...
+///   ASCII letters read as lowercase, since that is how every input
+///   source reports them, so `A` binds the same key as `a`.
+/// - Character keys that would be ambiguous, such as digits, `,`, `|` or
+///   whitespace, by code point: `U+002C`. Numeric keys above 9 as `Num12`.
+/// - Gamepad buttons: `Pad.South`, `Pad.Start`, `Pad.DPadUp`, ...
+/// - Gamepad axis directions: `Pad.LeftX+`, `Pad.LeftY-`, `Pad.RightTrigger+`, ...
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Binding {
+    /// A keyboard key or mouse button.
+    Key(Key),
+
+    /// A gamepad button.
+    Button(GamepadButton),
+
+    /// A gamepad axis pushed in the positive direction.
+    AxisPositive(GamepadAxis),
+
+    /// A gamepad axis pushed in the negative direction.
+    AxisNegative(GamepadAxis),
+}
+
+impl Binding {
+    /// Returns how strongly the binding is engaged, from `0.0` to `1.0`.
+    ///
+    /// Keys and buttons are either `0.0` or `1.0`; axis directions follow
+    /// the axis value with its deadzone applied.
+    pub fn strength(&self, input: &Input, gamepad: usize) -> f32 {
+        let pad = input.gamepad(gamepad);
+        let on = |pressed: bool| if pressed { 1.0 } else { 0.0 };
+        match *self {
+            Binding::Key(key) => on(input.pressed(key)),
+            Binding::Button(button) => on(pad.is_some_and(|pad| pad.pressed(button))),
+            Binding::AxisPositive(axis) => pad.map_or(0.0, |pad| pad.axis(axis).max(0.0)),
+            Binding::AxisNegative(axis) => pad.map_or(0.0, |pad| (-pad.axis(axis)).max(0.0)),
+        }
+    }
+
+    /// Returns `true` if the key or button went down this frame.
+    ///
+    /// Axis directions have no press edge of their own and always return
+    /// `false`.
+    pub fn just_pressed(&self, input: &Input, gamepad: usize) -> bool {
+        match *self {
+            Binding::Key(key) => input.just_pressed(key),
+            Binding::Button(button) => input.gamepad(gamepad).is_some_and(|pad| pad.just_pressed(button)),
+            Binding::AxisPositive(_) | Binding::AxisNegative(_) => false,
+        }
+    }
+}
+
+/// Keys whose binding name is their variant name.
+pub(crate) const NAMED_KEYS: [(Key, &str); 16] = [
+    (Key::Backspace, "Backspace"),
+    (Key::Enter, "Enter"),
+    (Key::Tab, "Tab"),
+    (Key::Escape, "Escape"),
+    (Key::LeftCtrl, "LeftCtrl"),
+    (Key::RightCtrl, "RightCtrl"),
+    (Key::LeftShift, "LeftShift"),
+    (Key::RightShift, "RightShift"),
+    (Key::Space, "Space"),
+    (Key::Up, "Up"),
+    (Key::Down, "Down"),
+    (Key::Left, "Left"),
+    (Key::Right, "Right"),
+    (Key::MouseLeft, "MouseLeft"),
+    (Key::MouseRight, "MouseRight"),
+    (Key::MouseMiddle, "MouseMiddle"),
+];
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(Key::Char(c)) if is_literal_char(*c) => write!(f, "{}", c),
+            Binding::Key(Key::Char(c)) => write!(f, "U+{:04X}", *c as u32),
+            Binding::Key(Key::Num(n)) if *n <= 9 => write!(f, "{}", n),
+            Binding::Key(Key::Num(n)) => write!(f, "Num{}", n),
+            Binding::Key(Key::F(n)) => write!(f, "F{}", n),
+            Binding::Key(key) => {
+                let (_, name) = NAMED_KEYS.iter().find(|(k, _)| k == key).ok_or(fmt::Error)?;
+                write!(f, "{}", name)
+            }
+            Binding::Button(button) => write!(f, "Pad.{:?}", button),
+            Binding::AxisPositive(axis) => write!(f, "Pad.{:?}+", axis),
+            Binding::AxisNegative(axis) => write!(f, "Pad.{:?}-", axis),
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = || format!("unknown binding `{}`", s);
+
+        if let Some(pad) = s.strip_prefix("Pad.") {
+            let axis = |name: &str| GamepadAxis::ALL.into_iter().find(|axis| format!("{:?}", axis) == name);
+            if let Some(name) = pad.strip_suffix('+') {
+                return axis(name).map(Binding::AxisPositive).ok_or_else(unknown);
+            }
+            if let Some(name) = pad.strip_suffix('-') {
+                return axis(name).map(Binding::AxisNegative).ok_or_else(unknown);
+            }
+            return GamepadButton::ALL
+                .into_iter()
+                .find(|button| format!("{:?}", button) == pad)
+                .map(Binding::Button)
+                .ok_or_else(unknown);
+        }
+
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return match c {
+                '0'..='9' => Ok(Binding::Key(Key::Num(c as u8 - b'0'))),
+                c if is_literal_char(c) => Ok(Binding::Key(Key::Char(c.to_ascii_lowercase()))),
+                _ => Err(unknown()),
+            };
+        }
+
+        if let Some(code) = s.strip_prefix("U+") {
+            return u32::from_str_radix(code, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(|c| Binding::Key(Key::Char(c.to_ascii_lowercase())))
+                .ok_or_else(unknown);
+        }
+        if let Some(n) = s.strip_prefix("Num").and_then(|n| n.parse::<u8>().ok()) {
+            return Ok(Binding::Key(Key::Num(n)));
+        }
+        if let Some(n) = s.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+            return Ok(Binding::Key(Key::F(n)));
+        }
+
+        NAMED_KEYS
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|&(key, _)| Binding::Key(key))
+            .ok_or_else(unknown)
+    }
+}
+
+/// Returns `true` if a character key is written as itself in the text
+/// form of a [`Binding`], rather than as a `U+` code point.
+fn is_literal_char(c: char) -> bool {
+    !c.is_whitespace() && !c.is_control() && !c.is_ascii_digit() && !matches!(c, ',' | '|' | '=' | '#')
+}
+
+/// Returns `true` if an action or axis name can be written to a config
+/// file and read back unchanged.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.trim() == name
+        && !name.starts_with('#')
+        && !name.contains(|c: char| c.is_control() || matches!(c, '=' | ',' | '|'))
+}
+
+/// Bindings of a single named entry.
+#[derive(Debug, Clone, PartialEq)]
+enum Bindings {
+    /// Any of the bindings triggers the action.
+    Action(Vec<Binding>),
+
+    /// Pairs of `(negative, positive)` bindings.
+    Axis(Vec<(Binding, Binding)>),
+}
+
+struct Entry {
+    name: String,
+    bindings: Bindings,
+    value: f32,
+    down: bool,
+    just_pressed: bool,
+    just_released: bool,
+    repeat: bool,
+    pressed_at: Duration,
+}
+
+/// Named actions and axes bound to keys and gamepad inputs.
+///
+/// Game code asks about `"jump"` or `"move_x"` instead of specific keys,
+/// so controls can be rebound at runtime and saved to a config file.
+/// Call [`ActionMap::update`] once per frame after
+/// [`crate::Window::update_controls`].
+///
+/// A name is either an action or an axis; binding it as the other kind
+/// replaces its bindings. Names must be non-empty, must not start or end
+/// with whitespace or start with `#`, and must not contain `=`, `,`, `|`
+/// or control characters, so that they survive [`ActionMap::save`].
+///
+/// # Example
+/// ```no_run
+/// use carose::Window;
+/// use carose::controls::{ActionMap, Binding, GamepadAxis, GamepadButton, Key};
+///
+/// let mut window = Window::new("Actions", 640, 480);
+/// let mut actions = ActionMap::new();
+/// actions.bind("jump", Binding::Key(Key::Space));
+/// actions.bind("jump", Binding::Button(GamepadButton::South));
+/// actions.bind_axis("move_x", Binding::Key(Key::Left), Binding::Key(Key::Right));
+/// actions.bind_axis(
+///     "move_x",
+///     Binding::AxisNegative(GamepadAxis::LeftX),
+///     Binding::AxisPositive(GamepadAxis::LeftX),
+/// );
+/// let _ = actions.load("bindings.cfg");
+///
+/// while window.is_open() {
+///     window.update_controls();
+///     actions.update(&window.controls);
+///
+///     let speed = actions.value("move_x") * 4.0;
+///     if actions.just_pressed("jump") {
+///         // jump
+///     }
+///     window.draw();
+/// }
+/// ```
+pub struct ActionMap {
+    entries: Vec<Entry>,
+
+    /// Gamepad slot read by gamepad bindings.
+    pub gamepad: usize,
+
+    /// Strength at which an action or axis counts as pressed.
+    pub threshold: f32,
+
+    /// Input time of the last update.
+    time: Duration,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActionMap {
+    /// Creates an empty map reading gamepad slot `0`, with a press
+    /// threshold of `0.5`.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            gamepad: 0,
+            threshold: 0.5,
+            time: Duration::ZERO,
+        }
+    }
+
+    /// Adds a binding to an action.
+    ///
+    /// # Panics
+    /// Panics if `action` is not a valid name (see [`ActionMap`]).
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        let entry = self.entry(action);
+        match &mut entry.bindings {
+            Bindings::Action(bindings) => {
+                if !bindings.contains(&binding) {
+                    bindings.push(binding);
+                }
+            }
+            Bindings::Axis(_) => entry.bindings = Bindings::Action(vec![binding]),
+        }
+    }
+
+    /// Adds a pair of bindings to an axis.
+    ///
+    /// The axis reads `-1.0` while only `negative` is engaged and `1.0`
+    /// while only `positive` is.
+    ///
+    /// # Panics
+    /// Panics if `axis` is not a valid name (see [`ActionMap`]).
+    pub fn bind_axis(&mut self, axis: &str, negative: Binding, positive: Binding) {
+        let entry = self.entry(axis);
+        match &mut entry.bindings {
+            Bindings::Axis(pairs) => {
+                if !pairs.contains(&(negative, positive)) {
+                    pairs.push((negative, positive));
+                }
+            }
+            Bindings::Action(_) => entry.bindings = Bindings::Axis(vec![(negative, positive)]),
+        }
+    }
+
+    /// Replaces every binding of an action.
+    ///
+    /// # Panics
+    /// Panics if `action` is not a valid name (see [`ActionMap`]).
+    pub fn rebind(&mut self, action: &str, bindings: Vec<Binding>) {
+        self.entry(action).bindings = Bindings::Action(bindings);
+    }
+
+    /// Replaces every binding pair of an axis.
+    ///
+    /// # Panics
+    /// Panics if `axis` is not a valid name (see [`ActionMap`]).
+    pub fn rebind_axis(&mut self, axis: &str, pairs: Vec<(Binding, Binding)>) {
+        self.entry(axis).bindings = Bindings::Axis(pairs);
+    }
+
+    /// Removes a binding from an action or axis, wherever it appears.
+    pub fn unbind(&mut self, name: &str, binding: Binding) {
+        let Some(entry) = self.entries.iter_mut().find(|e| e.name == name) else { return };
+        match &mut entry.bindings {
+            Bindings::Action(bindings) => bindings.retain(|&b| b != binding),
+            Bindings::Axis(pairs) => pairs.retain(|&(n, p)| n != binding && p != binding),
+        }
+    }
+
+    /// Returns the bindings of an action, or of both directions of an axis.
+    pub fn bindings(&self, name: &str) -> Vec<Binding> {
+        match self.entries.iter().find(|e| e.name == name).map(|e| &e.bindings) {
+            Some(Bindings::Action(bindings)) => bindings.clone(),
+            Some(Bindings::Axis(pairs)) => pairs.iter().flat_map(|&(n, p)| [n, p]).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the names of all actions and axes, in the order they were
+    /// first bound.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+
+    /// Samples the input for this frame.
+    ///
+    /// Press edges come from the bound keys and buttons, so a tap that
+    /// starts and ends within one frame is still reported. Auto-repeat
+    /// follows the input's [`Input::key_repeat`] timing.
+    pub fn update(&mut self, input: &Input) {
+        let (previous, now) = (self.time, input.time());
+        self.time = now;
+        let repeat = input.key_repeat();
+
+        for i in 0..self.entries.len() {
+            let value = self.evaluate(&self.entries[i].bindings, input);
+            let tapped = match &self.entries[i].bindings {
+                Bindings::Action(bindings) => bindings.iter().any(|b| b.just_pressed(input, self.gamepad)),
+                Bindings::Axis(pairs) => pairs
+                    .iter()
+                    .any(|(n, p)| n.just_pressed(input, self.gamepad) || p.just_pressed(input, self.gamepad)),
+            };
+
+            let entry = &mut self.entries[i];
+            let was_down = entry.down;
+            entry.value = value;
+            entry.down = value.abs() >= self.threshold;
+            entry.just_pressed = !was_down && (entry.down || tapped);
+            entry.just_released = !entry.down && (was_down || tapped);
+
+            if entry.just_pressed {
+                entry.pressed_at = now;
+            }
+            entry.repeat = entry.just_pressed
+                || (entry.down
+                    && repeat.count(now.saturating_sub(entry.pressed_at))
+                        > repeat.count(previous.saturating_sub(entry.pressed_at)));
+        }
+    }
+
+    /// Returns `true` while the action or axis is held.
+    pub fn pressed(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|e| e.down)
+    }
+
+    /// Returns `true` on the frame the action or axis became held.
+    pub fn just_pressed(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|e| e.just_pressed)
+    }
+
+    /// Returns `true` on the frame the action or axis stopped being held.
+    pub fn just_released(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|e| e.just_released)
+    }
+
+    /// Returns `true` on the frame the action became held and on every
+    /// auto-repeat while it stays held, e.g. for menu navigation.
+    pub fn repeated(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|e| e.repeat)
+    }
+
+    /// Returns how long the action or axis has been held, or zero if it
+    /// is not held.
+    pub fn held_duration(&self, name: &str) -> Duration {
+        self.find(name)
+            .filter(|e| e.down)
+            .map_or(Duration::ZERO, |e| self.time.saturating_sub(e.pressed_at))
+    }
+
+    /// Returns the value of an action or axis as of the last
+    /// [`Self::update`].
+    ///
+    /// Actions range from `0.0` to `1.0`, using their most engaged binding;
+    /// axes range from `-1.0` to `1.0`. Keys count as fully engaged, so
+    /// analog values only come from gamepad axes.
+    pub fn value(&self, name: &str) -> f32 {
+        self.find(name).map_or(0.0, |e| e.value)
+    }
+
+    /// Writes every binding to a config file.
+    ///
+    /// Each line holds one action (`jump = Space, Pad.South`) or axis
+    /// (`move_x = Left | Right, Pad.LeftX- | Pad.LeftX+`). An axis without
+    /// bindings is written as `move_x = |` so it loads back as an axis.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), BindingsError> {
+        let mut out = String::new();
+        for entry in &self.entries {
+            let bindings: Vec<String> = match &entry.bindings {
+                Bindings::Action(bindings) => bindings.iter().map(|b| b.to_string()).collect(),
+                Bindings::Axis(pairs) if pairs.is_empty() => vec!["|".to_string()],
+                Bindings::Axis(pairs) => pairs.iter().map(|(n, p)| format!("{} | {}", n, p)).collect(),
+            };
+            out.push_str(&format!("{} = {}\n", entry.name, bindings.join(", ")));
+        }
+        fs::write(path, out)?;
+        Ok(())
+    }
+
+    /// Reads bindings from a config file written by [`Self::save`].
+    ///
+    /// Names in the file replace the bindings of the same name; names not
+    /// in the file keep their current bindings, so defaults set up before
+    /// loading survive for actions added after the file was saved. Blank
+    /// lines and lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or a line is malformed.
+    /// Nothing is changed in that case.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<(), BindingsError> {
+        let text = fs::read_to_string(path)?;
+
+        let mut parsed = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| BindingsError::Parse { line: i + 1, message };
+
+            let (name, rest) = line.split_once('=').ok_or_else(|| error("expected `name = bindings`".to_string()))?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(error("missing name".to_string()));
+            }
+            if !is_valid_name(name) {
+                return Err(error(format!("invalid name `{}`", name)));
+            }
+
+            let items: Vec<&str> = rest.split(',').map(str::trim).filter(|s| !s.is_empty()).collect();
+            let bindings = if items == ["|"] {
+                Bindings::Axis(Vec::new())
+            } else if items.iter().any(|item| item.contains('|')) {
+                let mut pairs = Vec::new();
+                for item in items {
+                    let (n, p) = item.split_once('|').ok_or_else(|| error(format!("expected `negative | positive`, got `{}`", item)))?;
+                    pairs.push((n.trim().parse().map_err(error)?, p.trim().parse().map_err(error)?));
+                }
+                Bindings::Axis(pairs)
+            } else {
+                Bindings::Action(items.into_iter().map(str::parse).collect::<Result<_, _>>().map_err(error)?)
+            };
+            parsed.push((name.to_string(), bindings));
+        }
+
+        for (name, bindings) in parsed {
+            self.entry(&name).bindings = bindings;
+        }
+        Ok(())
+    }
+
+    fn find(&self, name: &str) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    /// Returns the entry for `name`, creating an empty action if needed.
+    ///
+    /// Panics if `name` is not a valid name.
+    fn entry(&mut self, name: &str) -> &mut Entry {
+        assert!(is_valid_name(name), "invalid action name `{}`", name);
+        let index = match self.entries.iter().position(|e| e.name == name) {
+            Some(index) => index,
+            None => {
+                self.entries.push(Entry {
+                    name: name.to_string(),
+                    bindings: Bindings::Action(Vec::new()),
+                    value: 0.0,
+                    down: false,
+                    just_pressed: false,
+                    just_released: false,
+                    repeat: false,
+                    pressed_at: Duration::ZERO,
+                });
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[index]
+    }
+
+    fn evaluate(&self, bindings: &Bindings, input: &Input) -> f32 {
+        let strength = |binding: &Binding| binding.strength(input, self.gamepad);
+        match bindings {
+            Bindings::Action(bindings) => bindings.iter().map(strength).fold(0.0, f32::max),
+            Bindings::Axis(pairs) => {
+                let negative = pairs.iter().map(|(n, _)| strength(n)).fold(0.0, f32::max);
+                let positive = pairs.iter().map(|(_, p)| strength(p)).fold(0.0, f32::max);
+                positive - negative
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controls::InputEvent;
+    use crate::testing::scratch_dir;
+
+    fn every_key() -> Vec<Key> {
+        let mut keys: Vec<Key> = NAMED_KEYS.iter().map(|&(key, _)| key).collect();
+        keys.extend(['a', 'z', 'é', 'É', '.', '+', '-', 'f', 'p'].map(Key::Char));
+        keys.extend(['0', '9', ',', '|', '=', '#', ' ', '\t'].map(Key::Char));
+        keys.extend([0, 5, 9, 10, 255].map(Key::Num));
+        keys.extend([0, 1, 12, 24, 255].map(Key::F));
+        keys
+    }
+
+    fn every_binding() -> Vec<Binding> {
+        let mut bindings: Vec<Binding> = every_key().into_iter().map(Binding::Key).collect();
+        bindings.extend(GamepadButton::ALL.map(Binding::Button));
+        bindings.extend(GamepadAxis::ALL.map(Binding::AxisPositive));
+        bindings.extend(GamepadAxis::ALL.map(Binding::AxisNegative));
+        bindings
+    }
+
+    /// Saves `map` and loads it into an empty map.
+    fn round_trip(map: &ActionMap, name: &str) -> ActionMap {
+        let path = scratch_dir(&format!("actions-{}", name)).join("bindings.cfg");
+        map.save(&path).unwrap();
+        let mut loaded = ActionMap::new();
+        let result = loaded.load(&path);
+        fs::remove_file(&path).unwrap();
+        result.unwrap();
+        loaded
+    }
+
+    fn entries(map: &ActionMap) -> Vec<(&str, &Bindings)> {
+        map.entries.iter().map(|e| (e.name.as_str(), &e.bindings)).collect()
+    }
+
+    #[test]
+    fn every_binding_text_round_trips() {
+        for binding in every_binding() {
+            let text = binding.to_string();
+            assert_eq!(text.parse::<Binding>(), Ok(binding), "text `{}`", text);
+        }
+    }
+
+    #[test]
+    fn text_forms() {
+        assert_eq!(Binding::Key(Key::Char('a')).to_string(), "a");
+        assert_eq!(Binding::Key(Key::Char('A')).to_string(), "A");
+        assert_eq!(Binding::Key(Key::Char(',')).to_string(), "U+002C");
+        assert_eq!(Binding::Key(Key::Char('1')).to_string(), "U+0031");
+        assert_eq!(Binding::Key(Key::Num(3)).to_string(), "3");
+        assert_eq!(Binding::Key(Key::Num(12)).to_string(), "Num12");
+        assert_eq!(Binding::Key(Key::F(5)).to_string(), "F5");
+        assert_eq!(Binding::Key(Key::Space).to_string(), "Space");
+        assert_eq!(Binding::Button(GamepadButton::South).to_string(), "Pad.South");
+        assert_eq!(Binding::AxisNegative(GamepadAxis::LeftX).to_string(), "Pad.LeftX-");
+        assert!("Pad.Nope".parse::<Binding>().is_err());
+        assert!("U+D800".parse::<Binding>().is_err());
+        assert!("Spacebar".parse::<Binding>().is_err());
+    }
+
+    #[test]
+    fn uppercase_letters_parse_as_the_key_backends_report() {
+        for text in ["A", "a", "U+0041", "U+0061"] {
+            assert_eq!(text.parse::<Binding>(), Ok(Binding::Key(Key::Char('a'))), "text `{}`", text);
+        }
+        assert_eq!("F".parse::<Binding>(), Ok(Binding::Key(Key::Char('f'))));
+
+        let mut input = Input::new();
+        let mut map = ActionMap::new();
+        map.bind("jump", "J".parse().unwrap());
+        input.begin_frame(true, Duration::ZERO);
+        input.handle_event(InputEvent::KeyDown(Key::Char('j')));
+        map.update(&input);
+        assert!(map.just_pressed("jump"));
+    }
+
+    #[test]
+    fn save_and_load_round_trip_every_binding() {
+        let mut map = ActionMap::new();
+        map.rebind("everything", every_binding());
+        let pairs = every_binding().into_iter().zip(every_binding().into_iter().rev()).collect();
+        map.rebind_axis("all pairs", pairs);
+        map.rebind("nothing", Vec::new());
+        map.rebind_axis("no axis", Vec::new());
+
+        let loaded = round_trip(&map, "every-binding");
+        assert_eq!(entries(&loaded), entries(&map));
+    }
+
+    #[test]
+    fn load_replaces_only_listed_names() {
+        let path = scratch_dir("actions-partial").join("bindings.cfg");
+        fs::write(&path, "# user bindings\n\njump = Pad.North\nmove_x = |\n").unwrap();
+
+        let mut map = ActionMap::new();
+        map.bind("jump", Binding::Key(Key::Space));
+        map.bind("fire", Binding::Key(Key::Char('x')));
+        map.bind("move_x", Binding::Key(Key::Right));
+        let result = map.load(&path);
+        fs::remove_file(&path).unwrap();
+        result.unwrap();
+
+        assert_eq!(map.bindings("jump"), [Binding::Button(GamepadButton::North)]);
+        assert_eq!(map.bindings("fire"), [Binding::Key(Key::Char('x'))]);
+        assert_eq!(entries(&map)[2].1, &Bindings::Axis(Vec::new()));
+    }
+
+    #[test]
+    fn malformed_files_change_nothing() {
+        for (text, line, message) in [
+            ("jump = Space\nfire = Nope\n", 2, "unknown binding `Nope`"),
+            ("jump Space\n", 1, "expected `name = bindings`"),
+            (" = Space\n", 1, "missing name"),
+            ("a,b = Space\n", 1, "invalid name `a,b`"),
+            ("x = a | \n", 1, "unknown binding ``"),
+        ] {
+            let path = scratch_dir("actions-malformed").join("bindings.cfg");
+            fs::write(&path, text).unwrap();
+            let mut map = ActionMap::new();
+            map.bind("jump", Binding::Key(Key::Enter));
+            let result = map.load(&path);
+            fs::remove_file(&path).unwrap();
+
+            match result {
+                Err(BindingsError::Parse { line: l, message: m }) => {
+                    assert_eq!((l, m.as_str()), (line, message), "file {:?}", text);
+                }
+                other => panic!("expected a parse error for {:?}, got {:?}", text, other),
+            }
+            assert_eq!(map.bindings("jump"), [Binding::Key(Key::Enter)]);
+            assert_eq!(map.names().count(), 1);
+        }
+    }
+
+    #[test]
+    fn name_rules() {
+        for name in ["jump", "move x", "Jump#2", "ü"] {
+            assert!(is_valid_name(name), "{:?}", name);
+        }
+        for name in ["", " jump", "jump ", "#jump", "a=b", "a,b", "a|b", "a\nb"] {
+            assert!(!is_valid_name(name), "{:?}", name);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid action name")]
+    fn binding_an_unwritable_name_panics() {
+        ActionMap::new().bind("left|right", Binding::Key(Key::Left));
+    }
+}
@@ -0,0 +1,641 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{InputEvent, KeyData, KeyRepeat};
+
+// Event types
+const EV_KEY: u16 = 0x01;
+const EV_ABS: u16 = 0x03;
+
+// Absolute axes (evdev codes)
+const ABS_X: u16 = 0x00;
+const ABS_Y: u16 = 0x01;
+const ABS_Z: u16 = 0x02;
+const ABS_RX: u16 = 0x03;
+const ABS_RY: u16 = 0x04;
+const ABS_RZ: u16 = 0x05;
+const ABS_HAT0X: u16 = 0x10;
+const ABS_HAT0Y: u16 = 0x11;
+
+// Gamepad buttons (evdev codes)
+const BTN_SOUTH: u16 = 0x130;
+const BTN_EAST: u16 = 0x131;
+const BTN_NORTH: u16 = 0x133;
+const BTN_WEST: u16 = 0x134;
+const BTN_TL: u16 = 0x136;
+const BTN_TR: u16 = 0x137;
+const BTN_TL2: u16 = 0x138;
+const BTN_TR2: u16 = 0x139;
+const BTN_SELECT: u16 = 0x13a;
+const BTN_START: u16 = 0x13b;
+const BTN_MODE: u16 = 0x13c;
+const BTN_THUMBL: u16 = 0x13d;
+const BTN_THUMBR: u16 = 0x13e;
+const BTN_DPAD_UP: u16 = 0x220;
+const BTN_DPAD_DOWN: u16 = 0x221;
+const BTN_DPAD_LEFT: u16 = 0x222;
+const BTN_DPAD_RIGHT: u16 = 0x223;
+
+/// Axes that report codes used by the standard layout.
+pub(crate) const AXIS_CODES: [u16; 8] = [ABS_X, ABS_Y, ABS_Z, ABS_RX, ABS_RY, ABS_RZ, ABS_HAT0X, ABS_HAT0Y];
+
+/// Default deadzone applied to stick axes.
+const STICK_DEADZONE: f32 = 0.15;
+
+/// A gamepad button in the standard layout.
+///
+/// Face buttons are named by position rather than label, so
+/// [`GamepadButton::South`] is A on Xbox pads and Cross on PlayStation pads.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// Bottom face button.
+    South,
+
+    /// Right face button.
+    East,
+
+    /// Left face button.
+    West,
+
+    /// Top face button.
+    North,
+
+    /// Left shoulder button.
+    LeftShoulder,
+
+    /// Right shoulder button.
+    RightShoulder,
+
+    /// Left trigger, when the pad reports it as a button.
+    LeftTrigger,
+
+    /// Right trigger, when the pad reports it as a button.
+    RightTrigger,
+
+    /// Select / Back / Share button.
+    Select,
+
+    /// Start / Options button.
+    Start,
+
+    /// Guide / Home button.
+    Mode,
+
+    /// Left stick click.
+    LeftStick,
+
+    /// Right stick click.
+    RightStick,
+
+    /// D-pad up.
+    DPadUp,
+
+    /// D-pad down.
+    DPadDown,
+
+    /// D-pad left.
+    DPadLeft,
+
+    /// D-pad right.
+    DPadRight,
+}
+
+/// A gamepad analog axis in the standard layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    /// Left stick, `-1.0` (left) to `1.0` (right).
+    LeftX,
+
+    /// Left stick, `-1.0` (up) to `1.0` (down).
+    LeftY,
+
+    /// Right stick, `-1.0` (left) to `1.0` (right).
+    RightX,
+
+    /// Right stick, `-1.0` (up) to `1.0` (down).
+    RightY,
+
+    /// Left trigger, `0.0` (released) to `1.0` (fully pressed).
+    LeftTrigger,
+
+    /// Right trigger, `0.0` (released) to `1.0` (fully pressed).
+    RightTrigger,
+}
+
+impl GamepadButton {
+    /// Every button of the standard layout.
+    pub const ALL: [GamepadButton; 17] = [
+        GamepadButton::South, GamepadButton::East, GamepadButton::West, GamepadButton::North,
+        GamepadButton::LeftShoulder, GamepadButton::RightShoulder,
+        GamepadButton::LeftTrigger, GamepadButton::RightTrigger,
+        GamepadButton::Select, GamepadButton::Start, GamepadButton::Mode,
+        GamepadButton::LeftStick, GamepadButton::RightStick,
+        GamepadButton::DPadUp, GamepadButton::DPadDown, GamepadButton::DPadLeft, GamepadButton::DPadRight,
+    ];
+}
+
+impl GamepadAxis {
+    /// Every axis of the standard layout.
+    pub const ALL: [GamepadAxis; 6] = [
+        GamepadAxis::LeftX, GamepadAxis::LeftY,
+        GamepadAxis::RightX, GamepadAxis::RightY,
+        GamepadAxis::LeftTrigger, GamepadAxis::RightTrigger,
+    ];
+
+    /// Returns `true` for triggers, which only range from `0.0` to `1.0`.
+    pub fn is_trigger(self) -> bool {
+        matches!(self, GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger)
+    }
+}
+
+/// State of a single gamepad.
+///
+/// Gamepads are identified by slot, in the order they were first seen.
+/// Obtain one through [`super::Input::gamepad`].
+pub struct Gamepad {
+    buttons: HashMap<GamepadButton, KeyData>,
+    axes: HashMap<GamepadAxis, f32>,
+    deadzones: HashMap<GamepadAxis, f32>,
+    time: Duration,
+}
+
+impl Default for Gamepad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Gamepad {
+    /// Creates a gamepad with every button released and every axis centred.
+    ///
+    /// Stick axes start with a deadzone of `0.15`; triggers have none.
+    pub fn new() -> Self {
+        let deadzones = [GamepadAxis::LeftX, GamepadAxis::LeftY, GamepadAxis::RightX, GamepadAxis::RightY]
+            .into_iter()
+            .map(|axis| (axis, STICK_DEADZONE))
+            .collect();
+
+        Self {
+            buttons: HashMap::new(),
+            axes: HashMap::new(),
+            deadzones,
+            time: Duration::ZERO,
+        }
+    }
+
+    /// Creates a gamepad whose first frame starts at input time `time`.
+    pub(crate) fn starting_at(time: Duration) -> Self {
+        Self { time, ..Self::new() }
+    }
+
+    pub fn update_button(&mut self, button: GamepadButton, is_down: bool) {
+        self.buttons.entry(button).or_insert_with(KeyData::new).update(is_down, self.time);
+    }
+
+    /// Sets the raw value of an axis, before the deadzone is applied.
+    pub fn update_axis(&mut self, axis: GamepadAxis, value: f32) {
+        let min = if axis.is_trigger() { 0.0 } else { -1.0 };
+        self.axes.insert(axis, value.clamp(min, 1.0));
+    }
+
+    pub fn pressed(&self, button: GamepadButton) -> bool {
+        self.buttons.get(&button).is_some_and(|b| b.pressed())
+    }
+
+    pub fn released(&self, button: GamepadButton) -> bool {
+        self.buttons.get(&button).is_none_or(|b| b.released())
+    }
+
+    /// Returns `true` once per press, after the button is released again.
+    pub fn clicked(&mut self, button: GamepadButton) -> bool {
+        self.buttons.get_mut(&button).is_some_and(|b| b.clicked())
+    }
+
+    /// Returns `true` during the frame the button went down.
+    pub fn just_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons.get(&button).is_some_and(|b| b.just_pressed)
+    }
+
+    /// Returns `true` during the frame the button went up.
+    pub fn just_released(&self, button: GamepadButton) -> bool {
+        self.buttons.get(&button).is_some_and(|b| b.just_released)
+    }
+
+    /// Returns `true` on the frame the button went down and on every
+    /// auto-repeat while it stays held.
+    pub fn repeated(&self, button: GamepadButton) -> bool {
+        self.buttons.get(&button).is_some_and(|b| b.repeated())
+    }
+
+    /// Returns how long the button has been held, or zero if it is released.
+    pub fn held_duration(&self, button: GamepadButton) -> Duration {
+        self.buttons.get(&button).map_or(Duration::ZERO, |b| b.held_duration(self.time))
+    }
+
+    /// Returns the value of an axis with its deadzone applied.
+    ///
+    /// Values inside the deadzone read as `0.0`; the remaining range is
+    /// rescaled so the axis still reaches `1.0` at full deflection.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        let value = self.raw_axis(axis);
+        let deadzone = self.deadzone(axis);
+        if value.abs() <= deadzone {
+            return 0.0;
+        }
+        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+    }
+
+    /// Returns the value of an axis as reported by the device.
+    pub fn raw_axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the deadzone of an axis.
+    pub fn deadzone(&self, axis: GamepadAxis) -> f32 {
+        self.deadzones.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    /// Sets the deadzone of an axis, clamped to `0.0..=0.99` so a fully
+    /// pushed axis still reads `1.0`.
+    pub fn set_deadzone(&mut self, axis: GamepadAxis, deadzone: f32) {
+        self.deadzones.insert(axis, deadzone.clamp(0.0, 0.99));
+    }
+
+    /// Fires auto-repeat for held buttons; edges are kept until
+    /// [`Self::clear_edges`].
+    pub(crate) fn begin_frame(&mut self, previous: Duration, now: Duration, repeat: KeyRepeat) {
+        self.time = now;
+        for button in self.buttons.values_mut() {
+            button.begin_frame(previous, now, repeat);
+        }
+    }
+
+    /// Clears the press, release and repeat edges of every button.
+    pub(crate) fn clear_edges(&mut self) {
+        for button in self.buttons.values_mut() {
+            button.clear_edges();
+        }
+    }
+
+    /// Releases every held button, reporting `just_released`, and centres
+    /// every axis.
+    pub(crate) fn release_all(&mut self) {
+        for button in self.buttons.values_mut() {
+            button.update(false, self.time);
+        }
+        self.axes.clear();
+    }
+
+    /// Forgets every button and axis, keeping deadzones.
+    pub(crate) fn reset(&mut self) {
+        self.buttons.clear();
+        self.axes.clear();
+    }
+
+    /// Appends the events that bring a reset pad in `slot` to this state.
+    pub(crate) fn state_events(&self, slot: usize, events: &mut Vec<InputEvent>) {
+        events.extend(
+            GamepadButton::ALL
+                .into_iter()
+                .filter(|&button| self.pressed(button))
+                .map(|button| InputEvent::PadButtonDown(slot, button)),
+        );
+        events.extend(
+            GamepadAxis::ALL
+                .into_iter()
+                .filter(|&axis| self.raw_axis(axis) != 0.0)
+                .map(|axis| InputEvent::PadAxis(slot, axis, self.raw_axis(axis))),
+        );
+    }
+}
+
+/// Translates raw evdev events from one gamepad into [`InputEvent`]s.
+///
+/// The Linux backend feeds it from `/dev/input/event*`, but it only deals
+/// in `(type, code, value)` triples, so recorded event streams (e.g. from
+/// `evtest`) can be replayed through it on any platform.
+///
+/// # Example
+/// ```
+/// use carose::controls::{EvdevDecoder, GamepadButton, GamepadAxis, Input};
+///
+/// // Press South, then push the left stick fully right.
+/// let recording = [(1, 0x130, 1), (3, 0x00, 32767)];
+///
+/// let mut decoder = EvdevDecoder::new(0);
+/// let mut events = Vec::new();
+/// for (type_, code, value) in recording {
+///     decoder.feed(type_, code, value, &mut events);
+/// }
+///
+/// let mut input = Input::new();
+/// for event in events {
+///     input.handle_event(event);
+/// }
+///
+/// let pad = input.gamepad(0).unwrap();
+/// assert!(pad.pressed(GamepadButton::South));
+/// assert_eq!(pad.axis(GamepadAxis::LeftX), 1.0);
+/// ```
+pub struct EvdevDecoder {
+    slot: usize,
+    ranges: HashMap<u16, (i32, i32)>,
+    hat: (i32, i32),
+}
+
+impl EvdevDecoder {
+    /// Creates a decoder reporting events for gamepad `slot`.
+    ///
+    /// Axes assume the common ranges until told otherwise: `-32768..=32767`
+    /// for sticks, `0..=255` for triggers and `-1..=1` for the D-pad hat.
+    pub fn new(slot: usize) -> Self {
+        let ranges = [
+            (ABS_X, (-32768, 32767)),
+            (ABS_Y, (-32768, 32767)),
+            (ABS_RX, (-32768, 32767)),
+            (ABS_RY, (-32768, 32767)),
+            (ABS_Z, (0, 255)),
+            (ABS_RZ, (0, 255)),
+            (ABS_HAT0X, (-1, 1)),
+            (ABS_HAT0Y, (-1, 1)),
+        ]
+        .into_iter()
+        .collect();
+
+        Self { slot, ranges, hat: (0, 0) }
+    }
+
+    /// Returns the gamepad slot this decoder reports.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// Sets the raw range of an absolute axis, as reported by the device.
+    pub fn set_axis_range(&mut self, code: u16, min: i32, max: i32) {
+        if min < max {
+            self.ranges.insert(code, (min, max));
+        }
+    }
+
+    /// Translates one raw event and appends the result to `events`.
+    ///
+    /// Events that have no meaning in the standard layout are ignored.
+    pub fn feed(&mut self, type_: u16, code: u16, value: i32, events: &mut Vec<InputEvent>) {
+        match type_ {
+            EV_KEY => {
+                if let Some(button) = map_button(code) {
+                    // 0 = release, 1 = press, 2 = auto-repeat while held
+                    events.push(if value != 0 {
+                        InputEvent::PadButtonDown(self.slot, button)
+                    } else {
+                        InputEvent::PadButtonUp(self.slot, button)
+                    });
+                }
+            }
+            EV_ABS => match code {
+                ABS_HAT0X => {
+                    let value = self.hat_direction(code, value);
+                    self.feed_hat(self.hat.0, value, GamepadButton::DPadLeft, GamepadButton::DPadRight, events);
+                    self.hat.0 = value;
+                }
+                ABS_HAT0Y => {
+                    let value = self.hat_direction(code, value);
+                    self.feed_hat(self.hat.1, value, GamepadButton::DPadUp, GamepadButton::DPadDown, events);
+                    self.hat.1 = value;
+                }
+                _ => {
+                    if let Some(axis) = map_axis(code) {
+                        events.push(InputEvent::PadAxis(self.slot, axis, self.normalize(code, axis, value)));
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Scales a raw axis value to `-1.0..=1.0`, or `0.0..=1.0` for triggers.
+    fn normalize(&self, code: u16, axis: GamepadAxis, value: i32) -> f32 {
+        let (min, max) = self.ranges.get(&code).copied().unwrap_or((-32768, 32767));
+        // Device ranges may span all of `i32`, so subtract in `f64`.
+        let unit = ((value as f64 - min as f64) / (max as f64 - min as f64)) as f32;
+        if axis.is_trigger() {
+            unit.clamp(0.0, 1.0)
+        } else {
+            (unit * 2.0 - 1.0).clamp(-1.0, 1.0)
+        }
+    }
+
+    /// Reduces a hat value to `-1`, `0` or `1` relative to its range.
+    fn hat_direction(&self, code: u16, value: i32) -> i32 {
+        let (min, max) = self.ranges.get(&code).copied().unwrap_or((-1, 1));
+        let centre = (min as i64 + max as i64) / 2;
+        (value as i64 - centre).signum() as i32
+    }
+
+    /// Emits D-pad button changes for one hat axis moving from `old` to `new`.
+    fn feed_hat(&self, old: i32, new: i32, negative: GamepadButton, positive: GamepadButton, events: &mut Vec<InputEvent>) {
+        if old == new {
+            return;
+        }
+        match old {
+            -1 => events.push(InputEvent::PadButtonUp(self.slot, negative)),
+            1 => events.push(InputEvent::PadButtonUp(self.slot, positive)),
+            _ => {}
+        }
+        match new {
+            -1 => events.push(InputEvent::PadButtonDown(self.slot, negative)),
+            1 => events.push(InputEvent::PadButtonDown(self.slot, positive)),
+            _ => {}
+        }
+    }
+}
+
+// Helper: map evdev button codes to the standard layout
+fn map_button(code: u16) -> Option<GamepadButton> {
+    Some(match code {
+        BTN_SOUTH => GamepadButton::South,
+        BTN_EAST => GamepadButton::East,
+        BTN_NORTH => GamepadButton::North,
+        BTN_WEST => GamepadButton::West,
+        BTN_TL => GamepadButton::LeftShoulder,
+        BTN_TR => GamepadButton::RightShoulder,
+        BTN_TL2 => GamepadButton::LeftTrigger,
+        BTN_TR2 => GamepadButton::RightTrigger,
+        BTN_SELECT => GamepadButton::Select,
+        BTN_START => GamepadButton::Start,
+        BTN_MODE => GamepadButton::Mode,
+        BTN_THUMBL => GamepadButton::LeftStick,
+        BTN_THUMBR => GamepadButton::RightStick,
+        BTN_DPAD_UP => GamepadButton::DPadUp,
+        BTN_DPAD_DOWN => GamepadButton::DPadDown,
+        BTN_DPAD_LEFT => GamepadButton::DPadLeft,
+        BTN_DPAD_RIGHT => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+// Helper: map evdev absolute axis codes to the standard layout
+fn map_axis(code: u16) -> Option<GamepadAxis> {
+    Some(match code {
+        ABS_X => GamepadAxis::LeftX,
+        ABS_Y => GamepadAxis::LeftY,
+        ABS_RX => GamepadAxis::RightX,
+        ABS_RY => GamepadAxis::RightY,
+        ABS_Z => GamepadAxis::LeftTrigger,
+        ABS_RZ => GamepadAxis::RightTrigger,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds one raw event and returns what it translated to.
+    fn feed(decoder: &mut EvdevDecoder, type_: u16, code: u16, value: i32) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        decoder.feed(type_, code, value, &mut events);
+        events
+    }
+
+    /// Feeds one absolute axis event and returns the normalized value.
+    fn axis_value(decoder: &mut EvdevDecoder, code: u16, value: i32) -> f32 {
+        match feed(decoder, EV_ABS, code, value)[..] {
+            [InputEvent::PadAxis(_, _, value)] => value,
+            ref other => panic!("expected one axis event, got {:?}", other),
+        }
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn hat_presses_and_releases_dpad_buttons() {
+        use GamepadButton::*;
+        use InputEvent::{PadButtonDown as Down, PadButtonUp as Up};
+
+        let mut decoder = EvdevDecoder::new(1);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0X, -1), [Down(1, DPadLeft)]);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0X, -1), []);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0X, 1), [Up(1, DPadLeft), Down(1, DPadRight)]);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0X, 0), [Up(1, DPadRight)]);
+
+        // The axes are independent, so diagonals hold two buttons.
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0Y, 1), [Down(1, DPadDown)]);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0X, 1), [Down(1, DPadRight)]);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0Y, -1), [Up(1, DPadDown), Down(1, DPadUp)]);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0Y, 0), [Up(1, DPadUp)]);
+    }
+
+    #[test]
+    fn hat_direction_follows_a_custom_range() {
+        let mut decoder = EvdevDecoder::new(0);
+        decoder.set_axis_range(ABS_HAT0X, 0, 2);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0X, 1), []);
+        assert_eq!(
+            feed(&mut decoder, EV_ABS, ABS_HAT0X, 0),
+            [InputEvent::PadButtonDown(0, GamepadButton::DPadLeft)],
+        );
+
+        decoder.set_axis_range(ABS_HAT0Y, i32::MIN, i32::MAX);
+        assert_eq!(feed(&mut decoder, EV_ABS, ABS_HAT0Y, 0), []);
+        assert_eq!(
+            feed(&mut decoder, EV_ABS, ABS_HAT0Y, i32::MAX),
+            [InputEvent::PadButtonDown(0, GamepadButton::DPadDown)],
+        );
+    }
+
+    #[test]
+    fn buttons_map_to_the_standard_layout() {
+        let mut decoder = EvdevDecoder::new(0);
+        assert_eq!(
+            feed(&mut decoder, EV_KEY, BTN_SOUTH, 1),
+            [InputEvent::PadButtonDown(0, GamepadButton::South)],
+        );
+        // Auto-repeat reports the button as still down.
+        assert_eq!(
+            feed(&mut decoder, EV_KEY, BTN_SOUTH, 2),
+            [InputEvent::PadButtonDown(0, GamepadButton::South)],
+        );
+        assert_eq!(
+            feed(&mut decoder, EV_KEY, BTN_SOUTH, 0),
+            [InputEvent::PadButtonUp(0, GamepadButton::South)],
+        );
+        assert_eq!(feed(&mut decoder, EV_KEY, 0x120, 1), []);
+        assert_eq!(feed(&mut decoder, EV_ABS, 0x28, 5), []);
+        assert_eq!(feed(&mut decoder, 0x00, 0, 0), []);
+    }
+
+    #[test]
+    fn axes_normalize_to_their_range() {
+        let mut decoder = EvdevDecoder::new(0);
+        assert_close(axis_value(&mut decoder, ABS_X, -32768), -1.0);
+        assert_close(axis_value(&mut decoder, ABS_X, 32767), 1.0);
+        assert_close(axis_value(&mut decoder, ABS_RY, 0), 0.0);
+        assert_close(axis_value(&mut decoder, ABS_Z, 255), 1.0);
+
+        decoder.set_axis_range(ABS_X, 0, 255);
+        assert_close(axis_value(&mut decoder, ABS_X, 0), -1.0);
+        assert_close(axis_value(&mut decoder, ABS_X, 255), 1.0);
+        assert_close(axis_value(&mut decoder, ABS_X, 51), -0.6);
+
+        decoder.set_axis_range(ABS_RZ, 0, 1023);
+        assert_close(axis_value(&mut decoder, ABS_RZ, 1023), 1.0);
+        assert_close(axis_value(&mut decoder, ABS_RZ, 0), 0.0);
+
+        // Ranges spanning all of `i32` do not overflow.
+        decoder.set_axis_range(ABS_RX, i32::MIN, i32::MAX);
+        assert_close(axis_value(&mut decoder, ABS_RX, i32::MIN), -1.0);
+        assert_close(axis_value(&mut decoder, ABS_RX, i32::MAX), 1.0);
+        assert_close(axis_value(&mut decoder, ABS_RX, 0), 0.0);
+        decoder.set_axis_range(ABS_Z, i32::MIN, i32::MAX);
+        assert_close(axis_value(&mut decoder, ABS_Z, i32::MAX), 1.0);
+
+        // An empty or inverted range is ignored.
+        decoder.set_axis_range(ABS_Y, 10, 10);
+        decoder.set_axis_range(ABS_Y, 10, -10);
+        assert_close(axis_value(&mut decoder, ABS_Y, 32767), 1.0);
+    }
+
+    #[test]
+    fn values_outside_the_range_are_clamped() {
+        let mut decoder = EvdevDecoder::new(0);
+        assert_close(axis_value(&mut decoder, ABS_Z, 400), 1.0);
+        assert_close(axis_value(&mut decoder, ABS_Z, -20), 0.0);
+
+        decoder.set_axis_range(ABS_RX, -100, 100);
+        assert_close(axis_value(&mut decoder, ABS_RX, 250), 1.0);
+        assert_close(axis_value(&mut decoder, ABS_RX, -250), -1.0);
+
+        let mut pad = Gamepad::new();
+        pad.update_axis(GamepadAxis::LeftTrigger, -0.5);
+        assert_eq!(pad.raw_axis(GamepadAxis::LeftTrigger), 0.0);
+        pad.update_axis(GamepadAxis::RightTrigger, 1.5);
+        assert_eq!(pad.raw_axis(GamepadAxis::RightTrigger), 1.0);
+        pad.update_axis(GamepadAxis::LeftX, -3.0);
+        assert_eq!(pad.raw_axis(GamepadAxis::LeftX), -1.0);
+    }
+
+    #[test]
+    fn deadzone_zeroes_small_values_and_rescales_the_rest() {
+        let mut pad = Gamepad::new();
+        assert_eq!(pad.deadzone(GamepadAxis::LeftX), STICK_DEADZONE);
+        assert_eq!(pad.deadzone(GamepadAxis::LeftTrigger), 0.0);
+
+        pad.update_axis(GamepadAxis::LeftX, 0.1);
+        assert_eq!(pad.axis(GamepadAxis::LeftX), 0.0);
+        pad.update_axis(GamepadAxis::LeftX, -0.575);
+        assert_close(pad.axis(GamepadAxis::LeftX), -0.5);
+        pad.update_axis(GamepadAxis::LeftX, 1.0);
+        assert_close(pad.axis(GamepadAxis::LeftX), 1.0);
+
+        pad.set_deadzone(GamepadAxis::LeftTrigger, 0.5);
+        pad.update_axis(GamepadAxis::LeftTrigger, 0.75);
+        assert_close(pad.axis(GamepadAxis::LeftTrigger), 0.5);
+        assert_close(pad.raw_axis(GamepadAxis::LeftTrigger), 0.75);
+
+        pad.set_deadzone(GamepadAxis::RightY, 2.0);
+        assert_eq!(pad.deadzone(GamepadAxis::RightY), 0.99);
+    }
+}
@@ -1,15 +1,15 @@
-use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::mem::{size_of, MaybeUninit};
-use std::os::unix::io::FromRawFd;
+use std::os::unix::io::{AsRawFd, FromRawFd};
 
 
-use super::{Key, KeyData};
+use super::gamepad::AXIS_CODES;
+use super::{EvdevDecoder, InputEvent, Key};
 
 #[repr(C)]
 #[derive(Copy, Clone)]
-struct InputEvent {
+struct RawEvent {
     tv_sec: i64,
     tv_usec: i64,
     type_: u16,
@@ -27,25 +27,86 @@ const BTN_LEFT: u16 = 272;
 const BTN_RIGHT: u16 = 273;
 const BTN_MIDDLE: u16 = 274;
 
+// Keyboard keys (evdev codes)
+const KEY_ESC: u16 = 1;
+const KEY_1: u16 = 2;
+const KEY_9: u16 = 10;
+const KEY_0: u16 = 11;
+const KEY_BACKSPACE: u16 = 14;
+const KEY_TAB: u16 = 15;
+const KEY_ENTER: u16 = 28;
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_LEFTSHIFT: u16 = 42;
+const KEY_RIGHTSHIFT: u16 = 54;
+const KEY_SPACE: u16 = 57;
+const KEY_F1: u16 = 59;
+const KEY_F10: u16 = 68;
+const KEY_F11: u16 = 87;
+const KEY_F12: u16 = 88;
+const KEY_KPENTER: u16 = 96;
+const KEY_RIGHTCTRL: u16 = 97;
+const KEY_UP: u16 = 103;
+const KEY_LEFT: u16 = 105;
+const KEY_RIGHT: u16 = 106;
+const KEY_DOWN: u16 = 108;
+
+// Letter keys follow the QWERTY rows, so their codes are not alphabetical.
+const LETTER_KEYS: [(u16, char); 26] = [
+    (16, 'q'), (17, 'w'), (18, 'e'), (19, 'r'), (20, 't'),
+    (21, 'y'), (22, 'u'), (23, 'i'), (24, 'o'), (25, 'p'),
+    (30, 'a'), (31, 's'), (32, 'd'), (33, 'f'), (34, 'g'),
+    (35, 'h'), (36, 'j'), (37, 'k'), (38, 'l'),
+    (44, 'z'), (45, 'x'), (46, 'c'), (47, 'v'), (48, 'b'),
+    (49, 'n'), (50, 'm'),
+];
+
 // Minimal FFI to avoid libc crate
 #[link(name = "c")]
 unsafe extern "C" {
     fn open(pathname: *const u8, flags: i32) -> i32;
+    fn ioctl(fd: i32, request: std::ffi::c_ulong, ...) -> i32;
+}
+
+// Mirrors `struct input_absinfo` from <linux/input.h>
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+struct AbsInfo {
+    value: i32,
+    minimum: i32,
+    maximum: i32,
+    fuzz: i32,
+    flat: i32,
+    resolution: i32,
+}
+
+// Capability queries: EVIOCGBIT(ev, len) and EVIOCGABS(abs)
+const KEY_BITS_LEN: usize = 0x300 / 8;
+const BTN_SOUTH: u16 = 0x130;
+
+fn eviocgbit(ev: u16, len: usize) -> std::ffi::c_ulong {
+    ioc_read(0x20 + ev as std::ffi::c_ulong, len)
+}
+
+fn eviocgabs(abs: u16) -> std::ffi::c_ulong {
+    ioc_read(0x40 + abs as std::ffi::c_ulong, size_of::<AbsInfo>())
+}
+
+// _IOR('E', nr, len)
+fn ioc_read(nr: std::ffi::c_ulong, len: usize) -> std::ffi::c_ulong {
+    (2 << 30) | ((len as std::ffi::c_ulong) << 16) | ((b'E' as std::ffi::c_ulong) << 8) | nr
 }
 
 // Open flags
 const O_RDONLY: i32 = 0;
+const O_NONBLOCK: i32 = 0o4000;
 
-pub struct Input {
-    keys: HashMap<Key, KeyData>,
-    cursor: (i32, i32),
-    scroll_delta: i32,
-    focused: bool,
+/// Raw evdev input source reading every `/dev/input/event*` device.
+pub(crate) struct NativeInput {
     devices: Vec<File>,
 }
 
-impl Input {
-    pub fn new() -> Self {
+impl NativeInput {
+    pub(crate) fn new() -> Self {
         let mut devices = Vec::new();
 
         // Open all /dev/input/event* devices
@@ -59,86 +120,103 @@ impl Input {
             }
         }
 
-        Self {
-            keys: HashMap::new(),
-            cursor: (0, 0),
-            scroll_delta: 0,
-            focused: true,
-            devices,
-        }
-    }
-
-    pub fn poll(&mut self, focused: bool) {
-        self.focused = focused;
-
-        if !focused {
-            self.keys.clear();
-            return;
-        }
-
-        self.update();
+        Self { devices }
     }
 
-    pub fn update(&mut self) {
-        self.scroll_delta = 0;
-
-        // Read all events first
-        let mut events = Vec::new();
+    /// Reads all pending device events and appends them to `events`.
+    ///
+    /// While unfocused, events are drained and dropped so presses made in
+    /// other windows are not replayed when focus returns.
+    pub(crate) fn poll(&mut self, focused: bool, events: &mut Vec<InputEvent>) {
         for dev in &mut self.devices {
             while let Some(ev) = read_event(dev) {
-                events.push(ev);
+                if focused && let Some(event) = translate_event(ev) {
+                    events.push(event);
+                }
             }
         }
-
-        // Then handle them after `self.devices` borrow ends
-        for ev in events {
-            self.handle_event(ev);
-        }
     }
+}
 
+/// Raw evdev source for every gamepad present when it was opened.
+pub(crate) struct NativeGamepads {
+    pads: Vec<(File, EvdevDecoder)>,
+}
 
-    fn handle_event(&mut self, ev: InputEvent) {
-        match ev.type_ {
-            EV_KEY => {
-                if let Some(key) = map_evdev_key(ev.code) {
-                    let is_down = ev.value != 0;
-                    self.update_key(key, is_down);
-                }
-            }
-            EV_REL if ev.code == REL_WHEEL => {
-                self.scroll_delta += ev.value;
+impl NativeGamepads {
+    /// Opens every `/dev/input/event*` device that reports gamepad buttons,
+    /// assigning slots in device order.
+    pub(crate) fn new() -> Self {
+        let mut pads = Vec::new();
+
+        for i in 0..32 {
+            let path = format!("/dev/input/event{}", i);
+            let Some(fd) = open_device(&path) else { continue };
+            // Safe: File takes ownership of fd
+            let dev = unsafe { File::from_raw_fd(fd) };
+
+            if let Some(decoder) = probe_gamepad(&dev, pads.len()) {
+                pads.push((dev, decoder));
             }
-            _ => {}
         }
-    }
 
-    pub fn update_key(&mut self, key: Key, is_down: bool) {
-        self.keys.entry(key).or_insert_with(KeyData::new).update(is_down);
+        Self { pads }
     }
 
-    pub fn pressed(&self, key: Key) -> bool {
-        self.keys.get(&key).map_or(false, |k| k.pressed())
+    /// Returns the number of gamepads found.
+    pub(crate) fn len(&self) -> usize {
+        self.pads.len()
     }
 
-    pub fn released(&self, key: Key) -> bool {
-        self.keys.get(&key).map_or(true, |k| k.released())
+    /// Reads all pending gamepad events and appends them to `events`.
+    ///
+    /// While unfocused, events are drained and dropped.
+    pub(crate) fn poll(&mut self, focused: bool, events: &mut Vec<InputEvent>) {
+        for (dev, decoder) in &mut self.pads {
+            while let Some(ev) = read_event(dev) {
+                if focused {
+                    decoder.feed(ev.type_, ev.code, ev.value, events);
+                }
+            }
+        }
     }
+}
 
-    pub fn clicked(&mut self, key: Key) -> bool {
-        self.keys.get_mut(&key).map_or(false, |k| k.clicked())
-    }
+// Helper: build a decoder if the device has gamepad buttons
+fn probe_gamepad(dev: &File, slot: usize) -> Option<EvdevDecoder> {
+    let fd = dev.as_raw_fd();
 
-    pub fn cursor_position(&self) -> (i32, i32) {
-        // No compositor-independent way; return fallback
-        self.cursor
+    let mut key_bits = [0u8; KEY_BITS_LEN];
+    let read = unsafe { ioctl(fd, eviocgbit(EV_KEY, KEY_BITS_LEN), key_bits.as_mut_ptr()) };
+    let byte = (BTN_SOUTH / 8) as usize;
+    if read <= byte as i32 || key_bits[byte] & (1 << (BTN_SOUTH % 8)) == 0 {
+        return None;
     }
 
-    pub fn scroll_delta(&self) -> i32 {
-        self.scroll_delta
+    let mut decoder = EvdevDecoder::new(slot);
+    for code in AXIS_CODES {
+        let mut info = AbsInfo::default();
+        if unsafe { ioctl(fd, eviocgabs(code), &mut info as *mut AbsInfo) } >= 0 {
+            decoder.set_axis_range(code, info.minimum, info.maximum);
+        }
     }
+    Some(decoder)
+}
 
-    pub fn end_frame(&mut self) {
-        self.scroll_delta = 0;
+// Helper: turn a raw evdev event into an input event
+fn translate_event(ev: RawEvent) -> Option<InputEvent> {
+    match ev.type_ {
+        EV_KEY => {
+            let key = map_evdev_key(ev.code)?;
+            // 0 = release, 1 = press, 2 = auto-repeat while held
+            Some(if ev.value != 0 {
+                InputEvent::KeyDown(key)
+            } else {
+                InputEvent::KeyUp(key)
+            })
+        }
+        EV_REL if ev.code == REL_WHEEL => Some(InputEvent::Scroll(ev.value)),
+        _ => None,
     }
 }
 
@@ -148,18 +226,42 @@ fn map_evdev_key(code: u16) -> Option<Key> {
         BTN_LEFT => Key::MouseLeft,
         BTN_RIGHT => Key::MouseRight,
         BTN_MIDDLE => Key::MouseMiddle,
-        _ => return None,
+
+        KEY_1..=KEY_9 => Key::Num((code - KEY_1 + 1) as u8),
+        KEY_0 => Key::Num(0),
+        KEY_F1..=KEY_F10 => Key::F((code - KEY_F1 + 1) as u8),
+        KEY_F11 => Key::F(11),
+        KEY_F12 => Key::F(12),
+
+        KEY_ESC => Key::Escape,
+        KEY_BACKSPACE => Key::Backspace,
+        KEY_TAB => Key::Tab,
+        KEY_ENTER | KEY_KPENTER => Key::Enter,
+        KEY_SPACE => Key::Space,
+        KEY_LEFTCTRL => Key::LeftCtrl,
+        KEY_RIGHTCTRL => Key::RightCtrl,
+        KEY_LEFTSHIFT => Key::LeftShift,
+        KEY_RIGHTSHIFT => Key::RightShift,
+        KEY_UP => Key::Up,
+        KEY_DOWN => Key::Down,
+        KEY_LEFT => Key::Left,
+        KEY_RIGHT => Key::Right,
+
+        _ => {
+            let &(_, c) = LETTER_KEYS.iter().find(|&&(key, _)| key == code)?;
+            Key::Char(c)
+        }
     })
 }
 
-// Read one InputEvent from a device
-fn read_event(dev: &mut File) -> Option<InputEvent> {
-    let mut ev = MaybeUninit::<InputEvent>::uninit();
+// Read one RawEvent from a device
+fn read_event(dev: &mut File) -> Option<RawEvent> {
+    let mut ev = MaybeUninit::<RawEvent>::uninit();
     let buf = unsafe {
-        std::slice::from_raw_parts_mut(ev.as_mut_ptr() as *mut u8, size_of::<InputEvent>())
+        std::slice::from_raw_parts_mut(ev.as_mut_ptr() as *mut u8, size_of::<RawEvent>())
     };
     match dev.read(buf) {
-        Ok(n) if n == size_of::<InputEvent>() => Some(unsafe { ev.assume_init() }),
+        Ok(n) if n == size_of::<RawEvent>() => Some(unsafe { ev.assume_init() }),
         _ => None,
     }
 }
@@ -167,7 +269,67 @@ fn read_event(dev: &mut File) -> Option<InputEvent> {
 // Open a device path using extern "C" open
 fn open_device(path: &str) -> Option<i32> {
     let c_path = std::ffi::CString::new(path).ok()?;
-    let fd = unsafe { open(c_path.as_ptr() as *const u8, O_RDONLY) };
+    let fd = unsafe { open(c_path.as_ptr() as *const u8, O_RDONLY | O_NONBLOCK) };
     if fd >= 0 { Some(fd) } else { None }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Codes are written out from linux/input-event-codes.h rather than
+    // taken from the constants above, so a wrong constant is caught too.
+    #[test]
+    fn evdev_codes_map_to_keys() {
+        let letters = [(16, 'q'), (25, 'p'), (30, 'a'), (38, 'l'), (44, 'z'), (50, 'm')];
+        for (code, c) in letters {
+            assert_eq!(map_evdev_key(code), Some(Key::Char(c)), "code {}", code);
+        }
+        let mut mapped: Vec<char> = LETTER_KEYS.iter().map(|&(code, _)| match map_evdev_key(code) {
+            Some(Key::Char(c)) => c,
+            other => panic!("code {} maps to {:?}", code, other),
+        }).collect();
+        mapped.sort_unstable();
+        assert_eq!(mapped, ('a'..='z').collect::<Vec<_>>());
+
+        for (code, digit) in [(2, 1), (6, 5), (10, 9), (11, 0)] {
+            assert_eq!(map_evdev_key(code), Some(Key::Num(digit)), "code {}", code);
+        }
+        let functions = [59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 87, 88];
+        for (n, code) in (1..).zip(functions) {
+            assert_eq!(map_evdev_key(code), Some(Key::F(n)), "code {}", code);
+        }
+
+        for (code, key) in [
+            (1, Key::Escape),
+            (14, Key::Backspace),
+            (15, Key::Tab),
+            (28, Key::Enter),
+            (96, Key::Enter),
+            (57, Key::Space),
+            (29, Key::LeftCtrl),
+            (97, Key::RightCtrl),
+            (42, Key::LeftShift),
+            (54, Key::RightShift),
+            (103, Key::Up),
+            (108, Key::Down),
+            (105, Key::Left),
+            (106, Key::Right),
+            (0x110, Key::MouseLeft),
+            (0x111, Key::MouseRight),
+            (0x112, Key::MouseMiddle),
+        ] {
+            assert_eq!(map_evdev_key(code), Some(key), "code {}", code);
+        }
+    }
+
+    #[test]
+    fn unknown_evdev_codes_are_ignored() {
+        // KEY_RESERVED, KEY_MINUS, KEY_LEFTALT, KEY_CAPSLOCK, KEY_F13,
+        // BTN_SIDE, BTN_SOUTH and a code past KEY_MAX.
+        for code in [0, 12, 56, 58, 183, 0x113, 0x130, 0x300] {
+            assert_eq!(map_evdev_key(code), None, "code {}", code);
+        }
+    }
+}
@@ -6,7 +6,17 @@ mod os;
 #[cfg_attr(target_os = "linux", path = "linux/mod.rs")]
 mod os;
 
-pub use os::{Input};
+mod actions;
+mod gamepad;
+mod recording;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub(crate) use os::{NativeGamepads, NativeInput};
+pub use actions::{ActionMap, Binding, BindingsError};
+pub use gamepad::{EvdevDecoder, Gamepad, GamepadAxis, GamepadButton};
+pub use recording::{InputRecording, RecordedFrame, RecordingError};
 
 /// Represents an abstract input key or button.
 ///
@@ -92,9 +102,9 @@ enum KeyState {
 
 /// Internal per-key state data.
 ///
-/// Stores both the current state and whether the key has been
-/// pressed since the last time it was released. This enables
-/// edge-triggered input such as clicks.
+/// Stores the current state, whether the key has been pressed since the
+/// last time it was released, and the per-frame edges. This enables
+/// edge-triggered input such as clicks, taps and key repeat.
 #[derive(Debug, Copy, Clone)]
 struct KeyData {
     /// Current physical state of the key.
@@ -102,6 +112,18 @@ struct KeyData {
 
     /// Whether the key was pressed since the last release.
     was_pressed: bool,
+
+    /// Whether the key went down during the current frame.
+    just_pressed: bool,
+
+    /// Whether the key went up during the current frame.
+    just_released: bool,
+
+    /// Input time at which the key last went down.
+    pressed_at: Duration,
+
+    /// Whether an auto-repeat fired at the start of the current frame.
+    repeat: bool,
 }
 
 impl KeyData {
@@ -110,27 +132,52 @@ impl KeyData {
         Self {
             state: KeyState::Released,
             was_pressed: false,
+            just_pressed: false,
+            just_released: false,
+            pressed_at: Duration::ZERO,
+            repeat: false,
         }
     }
 
     /// Updates the key state based on whether the key is currently down.
     ///
-    /// This method should be called once per frame during input polling.
-    /// It handles transitions between pressed and released states and
-    /// tracks press events for edge detection.
-    fn update(&mut self, is_down: bool) {
+    /// This method is called for every key event. It handles transitions
+    /// between pressed and released states and tracks press events for
+    /// edge detection. `now` is the current input time.
+    fn update(&mut self, is_down: bool, now: Duration) {
         match (self.state, is_down) {
             (KeyState::Released, true) => {
                 self.state = KeyState::Pressed;
                 self.was_pressed = true;
+                self.just_pressed = true;
+                self.pressed_at = now;
             }
             (KeyState::Pressed, false) => {
                 self.state = KeyState::Released;
+                self.just_released = true;
+                self.repeat = false;
             }
             _ => {}
         }
     }
 
+    /// Fires auto-repeat for the frame from `previous` to `now`, the input
+    /// times of the previous and current frame.
+    ///
+    /// Edges still set from earlier frames are kept; see [`Self::clear_edges`].
+    fn begin_frame(&mut self, previous: Duration, now: Duration, repeat: KeyRepeat) {
+        self.repeat |= self.pressed()
+            && repeat.count(now.saturating_sub(self.pressed_at))
+                > repeat.count(previous.saturating_sub(self.pressed_at));
+    }
+
+    /// Clears the press, release and repeat edges.
+    fn clear_edges(&mut self) {
+        self.just_pressed = false;
+        self.just_released = false;
+        self.repeat = false;
+    }
+
     /// Returns `true` if the key is currently held down.
     fn pressed(&self) -> bool {
         self.state == KeyState::Pressed
@@ -154,4 +201,581 @@ impl KeyData {
             false
         }
     }
+
+    /// Returns `true` on the frame the key went down and whenever it
+    /// auto-repeats while held.
+    fn repeated(&self) -> bool {
+        self.just_pressed || self.repeat
+    }
+
+    /// Returns how long the key has been held, or zero if it is released.
+    fn held_duration(&self, now: Duration) -> Duration {
+        if self.pressed() {
+            now.saturating_sub(self.pressed_at)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Auto-repeat timing for held keys and buttons.
+///
+/// A held key repeats once `delay` has passed since it went down, then
+/// `rate` times per second, like holding a key in a text editor.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KeyRepeat {
+    /// Time a key must be held before it starts repeating.
+    pub delay: Duration,
+
+    /// Repeats per second after the delay; `0.0` disables repeating.
+    pub rate: f32,
+}
+
+impl Default for KeyRepeat {
+    /// Repeats after 400 ms, then 12 times per second.
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(400),
+            rate: 12.0,
+        }
+    }
+}
+
+impl KeyRepeat {
+    /// Returns how many repeats have fired after holding for `held`.
+    pub(crate) fn count(&self, held: Duration) -> u64 {
+        if self.rate <= 0.0 || held < self.delay {
+            return 0;
+        }
+        ((held - self.delay).as_secs_f32() * self.rate) as u64 + 1
+    }
+}
+
+/// A single change in input state reported by an input source.
+///
+/// Sources translate whatever they observe (window events, raw devices)
+/// into these events, and [`Input`] folds them into per-key state.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InputEvent {
+    /// A key or mouse button went down.
+    KeyDown(Key),
+
+    /// A key or mouse button went up.
+    KeyUp(Key),
+
+    /// The cursor moved to a new position.
+    CursorMoved(i32, i32),
+
+    /// The scroll wheel moved; positive values scroll up.
+    Scroll(i32),
+
+    /// A character was typed.
+    Char(char),
+
+    /// A button went down on the gamepad in the given slot.
+    PadButtonDown(usize, GamepadButton),
+
+    /// A button went up on the gamepad in the given slot.
+    PadButtonUp(usize, GamepadButton),
+
+    /// An axis moved on the gamepad in the given slot.
+    ///
+    /// The value is normalized but has no deadzone applied.
+    PadAxis(usize, GamepadAxis, f32),
+}
+
+/// Selects where a [`crate::Window`] reads its input from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum InputBackend {
+    /// Key, mouse button, cursor, scroll and character events delivered to
+    /// the window itself.
+    ///
+    /// Input is only seen while the window is focused, the cursor is
+    /// reported relative to the window, and no special permissions are
+    /// needed. This is the default.
+    #[default]
+    Window,
+
+    /// Raw OS devices: `/dev/input/event*` on Linux and
+    /// `GetAsyncKeyState` on Windows.
+    ///
+    /// On Linux the user usually has to be in the `input` group for any
+    /// device to open. Typed characters and the cursor position still come
+    /// from the window.
+    Native,
+
+    /// No devices at all; input only comes from [`crate::Window::inject`].
+    ///
+    /// The window always counts as focused, so tests and demos driven
+    /// this way behave the same whether or not the window has focus.
+    Scripted,
+}
+
+/// The live input source of a window.
+pub(crate) enum InputSource {
+    /// Events come from the window's presentation backend.
+    Window,
+
+    /// Key, mouse and scroll events come from raw OS devices.
+    Native(NativeInput),
+
+    /// Only injected events are applied.
+    Scripted,
+}
+
+impl InputSource {
+    /// Opens the source selected by `backend`.
+    pub(crate) fn new(backend: InputBackend) -> Self {
+        match backend {
+            InputBackend::Window => InputSource::Window,
+            InputBackend::Native => InputSource::Native(NativeInput::new()),
+            InputBackend::Scripted => InputSource::Scripted,
+        }
+    }
+}
+
+/// Emits the key events that turn the `held` keys into the `down` keys.
+///
+/// Sources that can only observe which keys are currently down use this
+/// to report transitions; `held` is updated to the new set.
+pub(crate) fn diff_keys(held: &mut Vec<Key>, down: impl IntoIterator<Item = Key>, events: &mut Vec<InputEvent>) {
+    let mut now: Vec<Key> = Vec::new();
+    for key in down {
+        if !now.contains(&key) {
+            now.push(key);
+        }
+    }
+
+    events.extend(held.iter().filter(|key| !now.contains(key)).map(|&key| InputEvent::KeyUp(key)));
+    events.extend(now.iter().filter(|key| !held.contains(key)).map(|&key| InputEvent::KeyDown(key)));
+    *held = now;
+}
+
+/// Like [`diff_keys`], but only while `focused`.
+///
+/// While unfocused nothing is reported and `held` is cleared, matching the
+/// release [`Input::begin_frame`] applies, so keys still down when focus
+/// returns are reported as pressed again.
+pub(crate) fn diff_focused_keys(
+    held: &mut Vec<Key>,
+    focused: bool,
+    down: impl IntoIterator<Item = Key>,
+    events: &mut Vec<InputEvent>,
+) {
+    if focused {
+        diff_keys(held, down, events);
+    } else {
+        held.clear();
+    }
+}
+
+/// Frame-based keyboard and mouse state.
+///
+/// The window feeds this from its input source once per frame in
+/// [`crate::Window::update_controls`]; games then query it through
+/// [`crate::Window::controls`].
+pub struct Input {
+    keys: HashMap<Key, KeyData>,
+    cursor: (i32, i32),
+    scroll_delta: i32,
+    typed: Vec<char>,
+    gamepads: Vec<Gamepad>,
+    focused: bool,
+    time: Duration,
+    repeat: KeyRepeat,
+
+    /// Keeps edges across frames until [`Self::consume_edges`], so a
+    /// fixed-step loop that skips a frame still sees them.
+    pub(crate) latch_edges: bool,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self {
+            keys: HashMap::new(),
+            cursor: (0, 0),
+            scroll_delta: 0,
+            typed: Vec::new(),
+            gamepads: Vec::new(),
+            focused: true,
+            time: Duration::ZERO,
+            repeat: KeyRepeat::default(),
+            latch_edges: false,
+        }
+    }
+
+    /// Starts a new frame of input, `dt` after the previous one.
+    ///
+    /// Clears the typed characters, scroll delta and press/release edges
+    /// of the previous frame and fires auto-repeat for held keys. While
+    /// unfocused, every key and gamepad button is released; keys held when
+    /// focus is lost report [`Self::just_released`] on that frame, the
+    /// same as in [`ActionMap`].
+    ///
+    /// Inside [`crate::Window::run`] edges, typed characters and scroll are
+    /// instead kept until the next [`crate::windows::Game::update`] has
+    /// seen them.
+    pub fn begin_frame(&mut self, focused: bool, dt: Duration) {
+        let previous = self.time;
+        self.time += dt;
+        self.focused = focused;
+        if !self.latch_edges {
+            self.consume_edges();
+        }
+
+        for key in self.keys.values_mut() {
+            key.begin_frame(previous, self.time, self.repeat);
+        }
+        for pad in &mut self.gamepads {
+            pad.begin_frame(previous, self.time, self.repeat);
+        }
+
+        if !focused {
+            self.release_all();
+        }
+    }
+
+    /// Clears the press/release edges, auto-repeats, typed characters and
+    /// scroll delta gathered so far.
+    pub(crate) fn consume_edges(&mut self) {
+        self.typed.clear();
+        self.scroll_delta = 0;
+        for key in self.keys.values_mut() {
+            key.clear_edges();
+        }
+        for pad in &mut self.gamepads {
+            pad.clear_edges();
+        }
+    }
+
+    /// Returns the total input time, i.e. the sum of all frame deltas.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Returns the auto-repeat timing used by [`Self::repeated`].
+    pub fn key_repeat(&self) -> KeyRepeat {
+        self.repeat
+    }
+
+    /// Sets the auto-repeat timing used by [`Self::repeated`], for keys
+    /// and gamepad buttons alike.
+    pub fn set_key_repeat(&mut self, repeat: KeyRepeat) {
+        self.repeat = repeat;
+    }
+
+    /// Releases every held key and gamepad button and centres every axis.
+    ///
+    /// Keys and buttons go up as if their release events had arrived, so
+    /// they report [`Self::just_released`] for the current frame.
+    pub(crate) fn release_all(&mut self) {
+        for key in self.keys.values_mut() {
+            key.update(false, self.time);
+        }
+        for pad in &mut self.gamepads {
+            pad.release_all();
+        }
+    }
+
+    /// Forgets the state of every key, button and axis without reporting
+    /// any edges. Settings such as deadzones and key repeat are kept.
+    pub(crate) fn reset(&mut self) {
+        self.keys.clear();
+        for pad in &mut self.gamepads {
+            pad.reset();
+        }
+    }
+
+    /// Returns the events that bring a released [`Input`] to this state:
+    /// held keys and buttons, axis values and the cursor position.
+    pub(crate) fn state_events(&self) -> Vec<InputEvent> {
+        let mut events: Vec<InputEvent> = self
+            .keys
+            .iter()
+            .filter(|(_, data)| data.pressed())
+            .map(|(&key, _)| InputEvent::KeyDown(key))
+            .collect();
+        events.push(InputEvent::CursorMoved(self.cursor.0, self.cursor.1));
+        for (slot, pad) in self.gamepads.iter().enumerate() {
+            pad.state_events(slot, &mut events);
+        }
+        events
+    }
+
+    /// Applies a single input event to the current frame.
+    pub fn handle_event(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::KeyDown(key) => self.update_key(key, true),
+            InputEvent::KeyUp(key) => self.update_key(key, false),
+            InputEvent::CursorMoved(x, y) => self.cursor = (x, y),
+            InputEvent::Scroll(delta) => self.scroll_delta += delta,
+            InputEvent::Char(c) => self.push_char(c),
+            InputEvent::PadButtonDown(slot, button) => self.pad_slot(slot).update_button(button, true),
+            InputEvent::PadButtonUp(slot, button) => self.pad_slot(slot).update_button(button, false),
+            InputEvent::PadAxis(slot, axis, value) => self.pad_slot(slot).update_axis(axis, value),
+        }
+    }
+
+    /// Returns the gamepad in `slot`, adding empty slots up to it.
+    fn pad_slot(&mut self, slot: usize) -> &mut Gamepad {
+        if self.gamepads.len() <= slot {
+            let time = self.time;
+            self.gamepads.resize_with(slot + 1, || Gamepad::starting_at(time));
+        }
+        &mut self.gamepads[slot]
+    }
+
+    /// Returns the gamepad in `slot`, if any input has been seen from it.
+    pub fn gamepad(&self, slot: usize) -> Option<&Gamepad> {
+        self.gamepads.get(slot)
+    }
+
+    /// Returns the gamepad in `slot` mutably, e.g. to read clicks or
+    /// change deadzones.
+    pub fn gamepad_mut(&mut self, slot: usize) -> Option<&mut Gamepad> {
+        self.gamepads.get_mut(slot)
+    }
+
+    /// Returns the number of gamepad slots seen so far.
+    pub fn gamepad_count(&self) -> usize {
+        self.gamepads.len()
+    }
+
+    /// Sets the state of a key directly, as a [`InputEvent::KeyDown`] or
+    /// [`InputEvent::KeyUp`] would.
+    ///
+    /// Changes made here last until the input source reports the key
+    /// again; use [`crate::Window::inject`] to feed input through the
+    /// normal per-frame path instead.
+    pub fn update_key(&mut self, key: Key, is_down: bool) {
+        self.keys.entry(key).or_insert_with(KeyData::new).update(is_down, self.time);
+    }
+
+    pub fn pressed(&self, key: Key) -> bool {
+        self.keys.get(&key).is_some_and(|k| k.pressed())
+    }
+
+    pub fn released(&self, key: Key) -> bool {
+        self.keys.get(&key).is_none_or(|k| k.released())
+    }
+
+    /// Returns `true` once per press, after the key is released again.
+    ///
+    /// Prefer [`Self::just_pressed`] for actions that should happen as
+    /// soon as the key goes down, such as jumping or shooting.
+    pub fn clicked(&mut self, key: Key) -> bool {
+        self.keys.get_mut(&key).is_some_and(|k| k.clicked())
+    }
+
+    /// Returns `true` during the frame the key went down.
+    ///
+    /// A key tapped and released within a single frame still reports
+    /// `true` here, and also from [`Self::just_released`].
+    pub fn just_pressed(&self, key: Key) -> bool {
+        self.keys.get(&key).is_some_and(|k| k.just_pressed)
+    }
+
+    /// Returns `true` during the frame the key went up, including the
+    /// frame the window lost focus while the key was held.
+    pub fn just_released(&self, key: Key) -> bool {
+        self.keys.get(&key).is_some_and(|k| k.just_released)
+    }
+
+    /// Returns `true` on the frame the key went down and on every
+    /// auto-repeat while it stays held.
+    ///
+    /// Use this for menu navigation and text editing; the timing is set
+    /// with [`Self::set_key_repeat`].
+    pub fn repeated(&self, key: Key) -> bool {
+        self.keys.get(&key).is_some_and(|k| k.repeated())
+    }
+
+    /// Returns how long the key has been held, or zero if it is released.
+    pub fn held_duration(&self, key: Key) -> Duration {
+        self.keys.get(&key).map_or(Duration::ZERO, |k| k.held_duration(self.time))
+    }
+
+    /// Returns whether the window had focus when this frame started.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Returns the cursor position relative to the presentation target.
+    ///
+    /// Use [`crate::Window::mouse_position`] for frame pixels, which differ
+    /// once the window has been resized.
+    pub fn cursor_position(&self) -> (i32, i32) {
+        self.cursor
+    }
+
+    pub fn scroll_delta(&self) -> i32 {
+        self.scroll_delta
+    }
+
+    /// Returns the characters typed since the last poll, in order.
+    ///
+    /// Unlike key states, this follows the keyboard layout, shift state
+    /// and key repeat of the OS, so it is the right source for text entry.
+    pub fn typed_chars(&self) -> &[char] {
+        &self.typed
+    }
+
+    /// Appends a typed character to the current frame's stream.
+    ///
+    /// Control characters are ignored; editing keys such as
+    /// [`Key::Backspace`] are reported as key states instead.
+    pub fn push_char(&mut self, c: char) {
+        if !c.is_control() {
+            self.typed.push(c);
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        self.scroll_delta = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    #[test]
+    fn focus_loss_releases_held_keys_with_an_edge() {
+        let mut input = Input::new();
+        input.begin_frame(true, FRAME);
+        input.handle_event(InputEvent::KeyDown(Key::Space));
+        input.handle_event(InputEvent::PadButtonDown(0, GamepadButton::South));
+
+        input.begin_frame(false, FRAME);
+        assert!(!input.pressed(Key::Space));
+        assert!(input.just_released(Key::Space));
+        assert!(!input.repeated(Key::Space));
+        let pad = input.gamepad(0).unwrap();
+        assert!(!pad.pressed(GamepadButton::South));
+        assert!(pad.just_released(GamepadButton::South));
+
+        input.begin_frame(false, FRAME);
+        assert!(!input.just_released(Key::Space));
+    }
+
+    #[test]
+    fn keys_held_through_focus_loss_are_pressed_again() {
+        let mut input = Input::new();
+        let mut held = Vec::new();
+        let mut frame = |input: &mut Input, focused: bool, down: &[Key]| {
+            let mut events = Vec::new();
+            diff_focused_keys(&mut held, focused, down.iter().copied(), &mut events);
+            input.begin_frame(focused, FRAME);
+            if focused {
+                events.into_iter().for_each(|event| input.handle_event(event));
+            }
+        };
+
+        frame(&mut input, true, &[Key::Space]);
+        assert!(input.just_pressed(Key::Space));
+
+        frame(&mut input, false, &[Key::Space]);
+        assert!(input.just_released(Key::Space));
+
+        frame(&mut input, true, &[Key::Space]);
+        assert!(input.pressed(Key::Space));
+        assert!(input.just_pressed(Key::Space));
+
+        frame(&mut input, true, &[]);
+        assert!(input.just_released(Key::Space));
+    }
+
+    #[test]
+    fn focus_loss_matches_action_map() {
+        let mut input = Input::new();
+        let mut actions = ActionMap::new();
+        actions.bind("jump", Binding::Key(Key::Space));
+
+        input.begin_frame(true, FRAME);
+        input.handle_event(InputEvent::KeyDown(Key::Space));
+        actions.update(&input);
+
+        input.begin_frame(false, FRAME);
+        actions.update(&input);
+        assert_eq!(input.just_released(Key::Space), actions.just_released("jump"));
+        assert!(actions.just_released("jump"));
+    }
+
+    /// Holds Space from the first of `frames` frames of `dt` and returns
+    /// the frames on which it reported [`Input::repeated`].
+    fn repeat_frames(repeat: KeyRepeat, dt: Duration, frames: usize) -> Vec<usize> {
+        let mut input = Input::new();
+        input.set_key_repeat(repeat);
+        (0..frames)
+            .filter(|&frame| {
+                input.begin_frame(true, dt);
+                if frame == 0 {
+                    input.handle_event(InputEvent::KeyDown(Key::Space));
+                }
+                input.repeated(Key::Space)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn repeat_waits_for_the_delay_then_follows_the_rate() {
+        let repeat = KeyRepeat { delay: Duration::from_millis(500), rate: 4.0 };
+        let dt = Duration::from_millis(125);
+        assert_eq!(repeat_frames(repeat, dt, 10), [0, 4, 6, 8]);
+
+        // Frames longer than one repeat interval still repeat once each.
+        assert_eq!(repeat_frames(repeat, Duration::from_secs(1), 4), [0, 1, 2, 3]);
+
+        let off = KeyRepeat { rate: 0.0, ..repeat };
+        assert_eq!(repeat_frames(off, dt, 10), [0]);
+    }
+
+    #[test]
+    fn held_duration_grows_while_held_and_resets_on_release() {
+        let mut input = Input::new();
+        input.begin_frame(true, FRAME);
+        assert_eq!(input.held_duration(Key::Space), Duration::ZERO);
+
+        input.handle_event(InputEvent::KeyDown(Key::Space));
+        assert_eq!(input.held_duration(Key::Space), Duration::ZERO);
+        for frame in 1..=3 {
+            input.begin_frame(true, FRAME);
+            assert_eq!(input.held_duration(Key::Space), FRAME * frame);
+        }
+
+        input.handle_event(InputEvent::KeyUp(Key::Space));
+        assert_eq!(input.held_duration(Key::Space), Duration::ZERO);
+
+        input.begin_frame(true, FRAME);
+        input.handle_event(InputEvent::KeyDown(Key::Space));
+        input.begin_frame(true, FRAME);
+        assert_eq!(input.held_duration(Key::Space), FRAME);
+    }
+
+    #[test]
+    fn edges_last_exactly_one_frame() {
+        let mut input = Input::new();
+        let mut edges = Vec::new();
+        for frame in 0..6 {
+            input.begin_frame(true, FRAME);
+            match frame {
+                1 => input.handle_event(InputEvent::KeyDown(Key::Space)),
+                4 => input.handle_event(InputEvent::KeyUp(Key::Space)),
+                _ => {}
+            }
+            edges.push((input.just_pressed(Key::Space), input.just_released(Key::Space)));
+        }
+        assert_eq!(
+            edges,
+            [(false, false), (true, false), (false, false), (false, false), (false, true), (false, false)]
+        );
+    }
 }
@@ -0,0 +1,643 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use super::actions::NAMED_KEYS;
+use super::{GamepadAxis, GamepadButton, InputEvent, Key};
+
+/// Identifies recording files.
+const MAGIC: &[u8; 4] = b"CRIN";
+
+/// Current recording file format version.
+const VERSION: u8 = 2;
+
+/// Error returned when a recording cannot be loaded or saved.
+#[derive(Debug)]
+pub enum RecordingError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+
+    /// The file is not a valid recording.
+    Format(String),
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Io(err) => write!(f, "failed to access recording: {}", err),
+            RecordingError::Format(message) => write!(f, "invalid recording: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RecordingError::Io(err) => Some(err),
+            RecordingError::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RecordingError {
+    fn from(err: std::io::Error) -> Self {
+        RecordingError::Io(err)
+    }
+}
+
+/// Input of a single frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedFrame {
+    /// Duration of the frame before this one, as reported by the clock.
+    pub delta: Duration,
+
+    /// Whether the window had focus.
+    pub focused: bool,
+
+    /// Events applied during the frame, in order.
+    pub events: Vec<InputEvent>,
+}
+
+/// A frame-by-frame record of everything that reached [`super::Input`].
+///
+/// Record a session with [`crate::Window::start_recording`], save it to a
+/// compact binary file, and feed it back with
+/// [`crate::Window::play_recording`]. Playback replaces live devices and
+/// reproduces the frame durations seen by the window's clock, so a game
+/// that seeds its RNG the same way behaves identically, including on a
+/// headless window.
+///
+/// # Example
+/// ```no_run
+/// use carose::Window;
+/// use carose::controls::InputRecording;
+///
+/// let mut window = Window::headless(640, 480);
+/// window.play_recording(InputRecording::load("bug-report.rec").unwrap());
+///
+/// while window.is_replaying() {
+///     window.update_controls();
+///     // game logic, with the RNG seeded as in the recorded session
+///     window.draw();
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputRecording {
+    frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    /// Creates an empty recording.
+    pub fn new() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    /// Appends a frame.
+    pub fn push(&mut self, frame: RecordedFrame) {
+        self.frames.push(frame);
+    }
+
+    /// Returns all recorded frames in order.
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frames were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Writes the recording to a file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RecordingError> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Reads a recording written by [`Self::save`].
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a recording.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Encodes the recording in the file format used by [`Self::save`].
+    ///
+    /// Each frame stores a flags byte, its delta in nanoseconds unless it
+    /// matches the previous frame's, and its events. Counts and gamepad
+    /// slots are variable-length integers, so any value fits and idle
+    /// frames at a steady frame rate take 2 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        write_varint(self.frames.len() as u64, &mut out);
+
+        let mut delta = None;
+        for frame in &self.frames {
+            let mut flags = 0;
+            if frame.focused {
+                flags |= FLAG_FOCUSED;
+            }
+            if delta == Some(frame.delta) {
+                flags |= FLAG_SAME_DELTA;
+            }
+            out.push(flags);
+            if delta != Some(frame.delta) {
+                let nanos = frame.delta.as_nanos().min(u64::MAX as u128) as u64;
+                out.extend_from_slice(&nanos.to_le_bytes());
+                delta = Some(frame.delta);
+            }
+            write_varint(frame.events.len() as u64, &mut out);
+            for event in &frame.events {
+                encode_event(*event, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Decodes a recording from the file format used by [`Self::save`].
+    ///
+    /// # Errors
+    /// Returns [`RecordingError::Format`] if the data is not a recording.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RecordingError> {
+        let mut reader = Reader { bytes, pos: 0 };
+        if reader.take(4)? != MAGIC {
+            return Err(RecordingError::Format("missing header".to_string()));
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(RecordingError::Format(format!("unsupported version {}", version)));
+        }
+
+        let count = reader.varint()?;
+        let mut frames = Vec::new();
+        let mut delta = None;
+        for _ in 0..count {
+            let flags = reader.u8()?;
+            let focused = flags & FLAG_FOCUSED != 0;
+            let delta = match delta {
+                Some(previous) if flags & FLAG_SAME_DELTA != 0 => previous,
+                _ if flags & FLAG_SAME_DELTA != 0 => {
+                    return Err(RecordingError::Format("first frame has no delta".to_string()));
+                }
+                _ => *delta.insert(Duration::from_nanos(reader.u64()?)),
+            };
+            let events = (0..reader.varint()?)
+                .map(|_| decode_event(&mut reader))
+                .collect::<Result<_, _>>()?;
+            frames.push(RecordedFrame { delta, focused, events });
+        }
+
+        if reader.pos != bytes.len() {
+            return Err(RecordingError::Format("trailing data".to_string()));
+        }
+        Ok(Self { frames })
+    }
+}
+
+impl IntoIterator for InputRecording {
+    type Item = RecordedFrame;
+    type IntoIter = std::vec::IntoIter<RecordedFrame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.into_iter()
+    }
+}
+
+// Frame flags
+const FLAG_FOCUSED: u8 = 1;
+const FLAG_SAME_DELTA: u8 = 2;
+
+// Event tags
+const TAG_KEY_DOWN: u8 = 0;
+const TAG_KEY_UP: u8 = 1;
+const TAG_CURSOR: u8 = 2;
+const TAG_SCROLL: u8 = 3;
+const TAG_CHAR: u8 = 4;
+const TAG_PAD_DOWN: u8 = 5;
+const TAG_PAD_UP: u8 = 6;
+const TAG_PAD_AXIS: u8 = 7;
+
+// Key kinds
+const KEY_CHAR: u8 = 0;
+const KEY_NUM: u8 = 1;
+const KEY_F: u8 = 2;
+const KEY_NAMED: u8 = 3;
+
+fn encode_event(event: InputEvent, out: &mut Vec<u8>) {
+    match event {
+        InputEvent::KeyDown(key) => {
+            out.push(TAG_KEY_DOWN);
+            encode_key(key, out);
+        }
+        InputEvent::KeyUp(key) => {
+            out.push(TAG_KEY_UP);
+            encode_key(key, out);
+        }
+        InputEvent::CursorMoved(x, y) => {
+            out.push(TAG_CURSOR);
+            out.extend_from_slice(&x.to_le_bytes());
+            out.extend_from_slice(&y.to_le_bytes());
+        }
+        InputEvent::Scroll(delta) => {
+            out.push(TAG_SCROLL);
+            out.extend_from_slice(&delta.to_le_bytes());
+        }
+        InputEvent::Char(c) => {
+            out.push(TAG_CHAR);
+            out.extend_from_slice(&(c as u32).to_le_bytes());
+        }
+        InputEvent::PadButtonDown(slot, button) => {
+            out.push(TAG_PAD_DOWN);
+            write_varint(slot as u64, out);
+            out.push(button_index(button));
+        }
+        InputEvent::PadButtonUp(slot, button) => {
+            out.push(TAG_PAD_UP);
+            write_varint(slot as u64, out);
+            out.push(button_index(button));
+        }
+        InputEvent::PadAxis(slot, axis, value) => {
+            out.push(TAG_PAD_AXIS);
+            write_varint(slot as u64, out);
+            out.push(axis_index(axis));
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+fn decode_event(reader: &mut Reader) -> Result<InputEvent, RecordingError> {
+    Ok(match reader.u8()? {
+        TAG_KEY_DOWN => InputEvent::KeyDown(decode_key(reader)?),
+        TAG_KEY_UP => InputEvent::KeyUp(decode_key(reader)?),
+        TAG_CURSOR => InputEvent::CursorMoved(reader.i32()?, reader.i32()?),
+        TAG_SCROLL => InputEvent::Scroll(reader.i32()?),
+        TAG_CHAR => {
+            let code = reader.u32()?;
+            let c = char::from_u32(code).ok_or_else(|| RecordingError::Format(format!("invalid character {:#x}", code)))?;
+            InputEvent::Char(c)
+        }
+        TAG_PAD_DOWN => InputEvent::PadButtonDown(reader.slot()?, decode_button(reader)?),
+        TAG_PAD_UP => InputEvent::PadButtonUp(reader.slot()?, decode_button(reader)?),
+        TAG_PAD_AXIS => {
+            let slot = reader.slot()?;
+            let axis = decode_axis(reader)?;
+            InputEvent::PadAxis(slot, axis, f32::from_le_bytes(reader.array()?))
+        }
+        tag => return Err(RecordingError::Format(format!("unknown event tag {}", tag))),
+    })
+}
+
+/// Writes `value` as an unsigned LEB128 integer: 7 bits per byte, low
+/// bits first, with the top bit set on every byte but the last.
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn encode_key(key: Key, out: &mut Vec<u8>) {
+    let (kind, value) = match key {
+        Key::Char(c) => (KEY_CHAR, c as u32),
+        Key::Num(n) => (KEY_NUM, n as u32),
+        Key::F(n) => (KEY_F, n as u32),
+        key => (KEY_NAMED, NAMED_KEYS.iter().position(|&(k, _)| k == key).unwrap_or(0) as u32),
+    };
+    out.push(kind);
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn decode_key(reader: &mut Reader) -> Result<Key, RecordingError> {
+    let kind = reader.u8()?;
+    let value = reader.u32()?;
+    let invalid = || RecordingError::Format(format!("invalid key {}:{}", kind, value));
+    Ok(match kind {
+        KEY_CHAR => Key::Char(char::from_u32(value).ok_or_else(invalid)?),
+        KEY_NUM => Key::Num(u8::try_from(value).map_err(|_| invalid())?),
+        KEY_F => Key::F(u8::try_from(value).map_err(|_| invalid())?),
+        KEY_NAMED => NAMED_KEYS.get(value as usize).ok_or_else(invalid)?.0,
+        _ => return Err(invalid()),
+    })
+}
+
+fn button_index(button: GamepadButton) -> u8 {
+    GamepadButton::ALL.iter().position(|&b| b == button).unwrap_or(0) as u8
+}
+
+fn axis_index(axis: GamepadAxis) -> u8 {
+    GamepadAxis::ALL.iter().position(|&a| a == axis).unwrap_or(0) as u8
+}
+
+fn decode_button(reader: &mut Reader) -> Result<GamepadButton, RecordingError> {
+    let index = reader.u8()?;
+    GamepadButton::ALL
+        .get(index as usize)
+        .copied()
+        .ok_or_else(|| RecordingError::Format(format!("invalid gamepad button {}", index)))
+}
+
+fn decode_axis(reader: &mut Reader) -> Result<GamepadAxis, RecordingError> {
+    let index = reader.u8()?;
+    GamepadAxis::ALL
+        .get(index as usize)
+        .copied()
+        .ok_or_else(|| RecordingError::Format(format!("invalid gamepad axis {}", index)))
+}
+
+/// Little-endian cursor over a byte slice.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], RecordingError> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| RecordingError::Format("unexpected end of data".to_string()))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], RecordingError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, RecordingError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, RecordingError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, RecordingError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, RecordingError> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    /// Reads an integer written by [`write_varint`].
+    fn varint(&mut self) -> Result<u64, RecordingError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = (byte & 0x7F) as u64;
+            if bits << shift >> shift != bits {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(RecordingError::Format("integer too large".to_string()))
+    }
+
+    fn slot(&mut self) -> Result<usize, RecordingError> {
+        let slot = self.varint()?;
+        usize::try_from(slot).map_err(|_| RecordingError::Format(format!("invalid gamepad slot {}", slot)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_event() -> Vec<InputEvent> {
+        let mut keys = vec![Key::Char('a'), Key::Char('é'), Key::Num(0), Key::Num(9), Key::F(1), Key::F(12)];
+        keys.extend(NAMED_KEYS.iter().map(|&(key, _)| key));
+
+        let mut events: Vec<InputEvent> = keys
+            .into_iter()
+            .flat_map(|key| [InputEvent::KeyDown(key), InputEvent::KeyUp(key)])
+            .collect();
+        events.extend([
+            InputEvent::CursorMoved(-12, 480),
+            InputEvent::Scroll(-3),
+            InputEvent::Char('z'),
+            InputEvent::Char('ß'),
+            InputEvent::Char('🎮'),
+        ]);
+        for button in GamepadButton::ALL {
+            events.push(InputEvent::PadButtonDown(1, button));
+            events.push(InputEvent::PadButtonUp(1, button));
+        }
+        for axis in GamepadAxis::ALL {
+            events.push(InputEvent::PadAxis(2, axis, -0.25));
+        }
+        events
+    }
+
+    fn sample() -> InputRecording {
+        let mut recording = InputRecording::new();
+        recording.push(RecordedFrame { delta: Duration::ZERO, focused: true, events: every_event() });
+        recording.push(RecordedFrame { delta: Duration::from_nanos(16_666_667), focused: true, events: Vec::new() });
+        recording.push(RecordedFrame { delta: Duration::from_nanos(16_666_667), focused: false, events: Vec::new() });
+        recording.push(RecordedFrame { delta: Duration::from_secs(5), focused: true, events: vec![InputEvent::Scroll(1)] });
+        recording
+    }
+
+    fn format_error(bytes: &[u8]) -> String {
+        match InputRecording::from_bytes(bytes) {
+            Err(RecordingError::Format(message)) => message,
+            other => panic!("expected a format error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn empty_recording_round_trips() {
+        let recording = InputRecording::new();
+        let bytes = recording.to_bytes();
+        assert_eq!(bytes, [b'C', b'R', b'I', b'N', VERSION, 0]);
+        assert_eq!(InputRecording::from_bytes(&bytes).unwrap(), recording);
+    }
+
+    #[test]
+    fn every_event_round_trips() {
+        let recording = sample();
+        assert_eq!(InputRecording::from_bytes(&recording.to_bytes()).unwrap(), recording);
+    }
+
+    /// Keys, buttons and axes are stored as indices into lookup tables, so
+    /// a variant missing from its table would be saved as another one.
+    #[test]
+    fn lookup_tables_cover_every_variant() {
+        // The match is exhaustive, so a new variant fails to build here
+        // until it is added to the list as well.
+        let keys = [
+            Key::Char('a'), Key::Num(1), Key::F(1), Key::Backspace, Key::Enter, Key::Tab, Key::Escape,
+            Key::LeftCtrl, Key::RightCtrl, Key::LeftShift, Key::RightShift, Key::Space,
+            Key::Up, Key::Down, Key::Left, Key::Right, Key::MouseLeft, Key::MouseRight, Key::MouseMiddle,
+        ];
+        for key in keys {
+            match key {
+                Key::Char(_) | Key::Num(_) | Key::F(_) | Key::Backspace | Key::Enter | Key::Tab | Key::Escape
+                | Key::LeftCtrl | Key::RightCtrl | Key::LeftShift | Key::RightShift | Key::Space
+                | Key::Up | Key::Down | Key::Left | Key::Right
+                | Key::MouseLeft | Key::MouseRight | Key::MouseMiddle => {}
+            }
+        }
+        for button in GamepadButton::ALL {
+            match button {
+                GamepadButton::South | GamepadButton::East | GamepadButton::West | GamepadButton::North
+                | GamepadButton::LeftShoulder | GamepadButton::RightShoulder
+                | GamepadButton::LeftTrigger | GamepadButton::RightTrigger
+                | GamepadButton::Select | GamepadButton::Start | GamepadButton::Mode
+                | GamepadButton::LeftStick | GamepadButton::RightStick
+                | GamepadButton::DPadUp | GamepadButton::DPadDown | GamepadButton::DPadLeft | GamepadButton::DPadRight => {}
+            }
+        }
+        for axis in GamepadAxis::ALL {
+            match axis {
+                GamepadAxis::LeftX | GamepadAxis::LeftY | GamepadAxis::RightX | GamepadAxis::RightY
+                | GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => {}
+            }
+        }
+        // With the round trips below, the `ALL` lists hold as many distinct
+        // variants as the matches above name.
+        assert_eq!(GamepadButton::ALL.len(), 17);
+        assert_eq!(GamepadAxis::ALL.len(), 6);
+
+        let mut events: Vec<InputEvent> = keys.into_iter().map(InputEvent::KeyDown).collect();
+        events.extend(GamepadButton::ALL.map(|button| InputEvent::PadButtonDown(0, button)));
+        events.extend(GamepadAxis::ALL.map(|axis| InputEvent::PadAxis(0, axis, 1.0)));
+        for event in events {
+            let mut recording = InputRecording::new();
+            recording.push(RecordedFrame { delta: Duration::ZERO, focused: true, events: vec![event] });
+            let decoded = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+            assert_eq!(decoded.frames()[0].events, [event]);
+        }
+    }
+
+    #[test]
+    fn repeated_deltas_are_not_stored_again() {
+        let mut idle = InputRecording::new();
+        for _ in 0..10 {
+            idle.push(RecordedFrame { delta: Duration::from_millis(16), focused: true, events: Vec::new() });
+        }
+        // header + count, one frame with its delta, nine 2-byte frames
+        assert_eq!(idle.to_bytes().len(), 6 + 10 + 9 * 2);
+    }
+
+    #[test]
+    fn truncated_data_is_an_error() {
+        let bytes = sample().to_bytes();
+        for len in 0..bytes.len() {
+            assert!(
+                matches!(InputRecording::from_bytes(&bytes[..len]), Err(RecordingError::Format(_))),
+                "truncated to {} bytes",
+                len,
+            );
+        }
+    }
+
+    #[test]
+    fn bad_header_is_an_error() {
+        let mut bytes = InputRecording::new().to_bytes();
+        bytes[0] = b'X';
+        assert_eq!(format_error(&bytes), "missing header");
+
+        let mut bytes = InputRecording::new().to_bytes();
+        bytes[4] = VERSION + 1;
+        assert_eq!(format_error(&bytes), format!("unsupported version {}", VERSION + 1));
+    }
+
+    #[test]
+    fn trailing_bytes_are_an_error() {
+        let mut bytes = sample().to_bytes();
+        bytes.push(0);
+        assert_eq!(format_error(&bytes), "trailing data");
+    }
+
+    #[test]
+    fn invalid_values_are_errors() {
+        let frame = |event: &[u8]| {
+            let mut bytes = InputRecording::new().to_bytes();
+            bytes[5] = 1;
+            bytes.push(FLAG_FOCUSED);
+            bytes.extend_from_slice(&0u64.to_le_bytes());
+            bytes.push(1);
+            bytes.extend_from_slice(event);
+            bytes
+        };
+
+        assert_eq!(format_error(&frame(&[9])), "unknown event tag 9");
+        assert_eq!(format_error(&frame(&[TAG_KEY_DOWN, 7, 0, 0, 0, 0])), "invalid key 7:0");
+        assert_eq!(format_error(&frame(&[TAG_KEY_DOWN, KEY_NUM, 0, 1, 0, 0])), "invalid key 1:256");
+        assert_eq!(format_error(&frame(&[TAG_KEY_UP, KEY_NAMED, 16, 0, 0, 0])), "invalid key 3:16");
+        assert_eq!(format_error(&frame(&[TAG_CHAR, 0, 0xD8, 0, 0])), "invalid character 0xd800");
+        assert_eq!(format_error(&frame(&[TAG_PAD_DOWN, 0, 17])), "invalid gamepad button 17");
+        assert_eq!(format_error(&frame(&[TAG_PAD_AXIS, 0, 6, 0, 0, 0, 0])), "invalid gamepad axis 6");
+
+        let mut same_delta_first = InputRecording::new().to_bytes();
+        same_delta_first[5] = 1;
+        same_delta_first.extend_from_slice(&[FLAG_SAME_DELTA, 0]);
+        assert_eq!(format_error(&same_delta_first), "first frame has no delta");
+
+        let mut huge_count = InputRecording::new().to_bytes();
+        huge_count.truncate(5);
+        huge_count.extend_from_slice(&[0xFF; 9]);
+        huge_count.push(0x02);
+        assert_eq!(format_error(&huge_count), "integer too large");
+    }
+
+    #[test]
+    fn large_counts_and_slots_round_trip() {
+        let mut events = vec![InputEvent::Scroll(1); 70_000];
+        events.extend([
+            InputEvent::PadButtonDown(300, GamepadButton::South),
+            InputEvent::PadButtonUp(128, GamepadButton::South),
+            InputEvent::PadAxis(usize::MAX, GamepadAxis::LeftX, 0.5),
+        ]);
+        let mut recording = InputRecording::new();
+        recording.push(RecordedFrame { delta: Duration::ZERO, focused: true, events });
+
+        assert_eq!(InputRecording::from_bytes(&recording.to_bytes()).unwrap(), recording);
+    }
+
+    #[test]
+    fn varints_use_seven_bits_per_byte() {
+        for (value, encoded) in [
+            (0, vec![0x00]),
+            (127, vec![0x7F]),
+            (128, vec![0x80, 0x01]),
+            (65_535, vec![0xFF, 0xFF, 0x03]),
+            (u64::MAX, [vec![0xFF; 9], vec![0x01]].concat()),
+        ] {
+            let mut out = Vec::new();
+            write_varint(value, &mut out);
+            assert_eq!(out, encoded);
+            assert_eq!(Reader { bytes: &out, pos: 0 }.varint().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn save_and_load_use_the_same_format() {
+        let path = crate::testing::scratch_dir("recording").join("input.rec");
+        let recording = sample();
+        recording.save(&path).unwrap();
+        let loaded = InputRecording::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), recording);
+
+        assert!(matches!(InputRecording::load(&path), Err(RecordingError::Io(_))));
+    }
+}
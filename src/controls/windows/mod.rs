@@ -1,13 +1,11 @@
-use std::collections::HashMap;
 use std::mem::MaybeUninit;
-use super::{Key, KeyData};
+use super::{diff_keys, InputEvent, Key};
 const WM_MOUSEWHEEL: u32 = 0x020A;
 const PM_REMOVE: u32 = 0x0001;
 
 #[link(name = "user32")]
 unsafe extern "system" {
     fn GetAsyncKeyState(vkey: i32) -> i16;
-    fn GetCursorPos(point: *mut POINT) -> i32;
     fn PeekMessageW(
         msg: *mut MSG,
         hwnd: isize,
@@ -37,80 +35,72 @@ fn vk_down(vk: i32) -> bool {
     unsafe { (GetAsyncKeyState(vk) & 0x8000u16 as i16) != 0 }
 }
 
-pub struct Input {
-    keys: HashMap<Key, KeyData>,
-    cursor: (i32, i32),
-    scroll_delta: i32,
-    focused: bool
+/// Input source polling the global key state through `GetAsyncKeyState`.
+pub(crate) struct NativeInput {
+    held: Vec<Key>,
 }
 
-impl Input {
-    pub fn new() -> Self {
-        Self {
-            keys: HashMap::new(),
-            cursor: (0, 0),
-            scroll_delta: 0,
-            focused: false
-        }
+impl NativeInput {
+    pub(crate) fn new() -> Self {
+        Self { held: Vec::new() }
     }
-    pub fn poll(&mut self, focused: bool) {
-        self.focused = focused;
 
+    /// Polls keyboard, mouse buttons and wheel and appends the changes
+    /// since the last poll to `events`.
+    ///
+    /// While unfocused nothing is reported, and every key counts as
+    /// released so held keys are pressed again when focus returns.
+    pub(crate) fn poll(&mut self, focused: bool, events: &mut Vec<InputEvent>) {
         if !focused {
-            self.keys.clear();
+            self.held.clear();
             return;
         }
 
-        self.update(); // keyboard + mouse + cursor + wheel
-    }
-
-    pub fn update(&mut self) {
         // -------- Keyboard --------
 
+        let mut down = Vec::new();
+        let mut check = |key: Key, vk: i32| {
+            if vk_down(vk) {
+                down.push(key);
+            }
+        };
+
         for vk in 0x41..=0x5A {
             let c = (vk as u8 as char).to_ascii_lowercase();
-            self.update_key(Key::Char(c), vk_down(vk));
+            check(Key::Char(c), vk);
         }
 
         for vk in 0x30..=0x39 {
-            self.update_key(Key::Num((vk - 0x30) as u8), vk_down(vk));
+            check(Key::Num((vk - 0x30) as u8), vk);
         }
 
         for i in 0..12 {
-            self.update_key(Key::F(i + 1), vk_down((0x70 + i).into()));
+            check(Key::F(i + 1), (0x70 + i).into());
         }
 
-        self.update_key(Key::Space, vk_down(0x20));
-        self.update_key(Key::Backspace, vk_down(0x08));
-        self.update_key(Key::Tab, vk_down(0x09));
-        self.update_key(Key::Enter, vk_down(0x0D));
-        self.update_key(Key::Escape, vk_down(0x1B));
+        check(Key::Space, 0x20);
+        check(Key::Backspace, 0x08);
+        check(Key::Tab, 0x09);
+        check(Key::Enter, 0x0D);
+        check(Key::Escape, 0x1B);
 
-        self.update_key(Key::LeftShift, vk_down(0xA0));
-        self.update_key(Key::RightShift, vk_down(0xA1));
-        self.update_key(Key::LeftCtrl, vk_down(0xA2));
-        self.update_key(Key::RightCtrl, vk_down(0xA3));
+        check(Key::LeftShift, 0xA0);
+        check(Key::RightShift, 0xA1);
+        check(Key::LeftCtrl, 0xA2);
+        check(Key::RightCtrl, 0xA3);
 
-        self.update_key(Key::Up, vk_down(0x26));
-        self.update_key(Key::Down, vk_down(0x28));
-        self.update_key(Key::Left, vk_down(0x25));
-        self.update_key(Key::Right, vk_down(0x27));
+        check(Key::Up, 0x26);
+        check(Key::Down, 0x28);
+        check(Key::Left, 0x25);
+        check(Key::Right, 0x27);
 
         // -------- Mouse Buttons --------
 
-        self.update_key(Key::MouseLeft, vk_down(0x01));
-        self.update_key(Key::MouseRight, vk_down(0x02));
-        self.update_key(Key::MouseMiddle, vk_down(0x04));
+        check(Key::MouseLeft, 0x01);
+        check(Key::MouseRight, 0x02);
+        check(Key::MouseMiddle, 0x04);
 
-        // -------- Cursor Position --------
-
-        unsafe {
-            let mut pt = MaybeUninit::<POINT>::zeroed();
-            if GetCursorPos(pt.as_mut_ptr()) != 0 {
-                let pt = pt.assume_init();
-                self.cursor = (pt.x, pt.y);
-            }
-        }
+        diff_keys(&mut self.held, down, events);
 
         // -------- Scroll Wheel --------
 
@@ -126,39 +116,24 @@ impl Input {
             {
                 let msg = msg.assume_init_read();
                 let delta = ((msg.wparam >> 16) & 0xFFFF) as i16;
-                self.scroll_delta += delta as i32;
+                events.push(InputEvent::Scroll(delta as i32));
             }
         }
     }
+}
 
-    fn update_key(&mut self, key: Key, is_down: bool) {
-        self.keys
-            .entry(key)
-            .or_insert_with(KeyData::new)
-            .update(is_down);
-    }
-        pub fn pressed(&self, key: Key) -> bool {
-        self.keys.get(&key).map_or(false, |k| k.pressed())
-    }
-
-    pub fn released(&self, key: Key) -> bool {
-        self.keys.get(&key).map_or(true, |k| k.released())
-    }
-
-    pub fn clicked(&mut self, key: Key) -> bool {
-        self.keys.get_mut(&key).map_or(false, |k| k.clicked())
-    }
+/// Gamepad source; gamepads are read through evdev, which Windows lacks,
+/// so no pads are ever found.
+pub(crate) struct NativeGamepads;
 
-    pub fn cursor_position(&self) -> (i32, i32) {
-        self.cursor
+impl NativeGamepads {
+    pub(crate) fn new() -> Self {
+        Self
     }
 
-    pub fn scroll_delta(&self) -> i32 {
-        self.scroll_delta
+    pub(crate) fn len(&self) -> usize {
+        0
     }
 
-    pub fn end_frame(&mut self) {
-        self.scroll_delta = 0;
-    }
+    pub(crate) fn poll(&mut self, _focused: bool, _events: &mut Vec<InputEvent>) {}
 }
-
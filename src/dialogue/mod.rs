@@ -0,0 +1,367 @@
+use std::collections::VecDeque;
+use crate::{Window, TextAlign, Layer};
+use crate::audio::{BuiltInSound, EffectStream, SoundSource};
+use crate::controls::Key;
+use crate::sprites::SpriteId;
+use crate::windows::text::measure_text;
+use crate::windows::text_box::{visible_chars, TextBox};
+
+/// Seconds the continue indicator stays on or off while blinking.
+const BLINK_INTERVAL: f32 = 0.4;
+
+/// Gap in pixels between the portrait, speaker name and text.
+const GAP: usize = 8;
+
+/// One page of dialogue.
+pub struct Page {
+    /// Text of the page, which may contain `\n` and color markup.
+    pub text: String,
+
+    /// Name shown above the text, if any.
+    pub speaker: Option<String>,
+
+    /// Sprite shown to the left of the text, if any.
+    pub portrait: Option<SpriteId>,
+}
+
+impl Page {
+    /// Creates a page with no speaker or portrait.
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            speaker: None,
+            portrait: None,
+        }
+    }
+
+    /// Sets the speaker name shown above the text.
+    pub fn with_speaker(mut self, speaker: &str) -> Self {
+        self.speaker = Some(speaker.to_string());
+        self
+    }
+
+    /// Sets the portrait sprite shown to the left of the text.
+    pub fn with_portrait(mut self, portrait: SpriteId) -> Self {
+        self.portrait = Some(portrait);
+        self
+    }
+}
+
+/// A queue of dialogue pages revealed with a typewriter effect.
+///
+/// Each page's text appears character by character. Pressing one of
+/// [`Dialogue::keys`] while a page is still being revealed shows it in full;
+/// pressing it again moves on to the next page. Once a page is fully shown,
+/// a blinking indicator appears in the bottom-right corner of the text box.
+///
+/// Like [`crate::Menu`], the dialogue renders itself through text items
+/// whose ids start with a prefix chosen by the caller.
+///
+/// # Example
+/// ```no_run
+/// use carose::{Window, Dialogue};
+/// use carose::dialogue::Page;
+/// use carose::windows::text_box::TextBox;
+///
+/// let mut window = Window::new("Dialogue", 640, 480);
+/// let mut dialogue = Dialogue::new((20, 360), TextBox::new(600, 80));
+/// dialogue.push(Page::new("Welcome, traveler.").with_speaker("Elder"));
+/// dialogue.push(Page::new("The [color=#ff0000]cave[/color] lies to the east."));
+///
+/// while window.is_open() && dialogue.is_active() {
+///     window.update_controls();
+///     dialogue.update(&mut window);
+///     dialogue.draw(&mut window, "elder");
+///     window.draw();
+/// }
+/// dialogue.draw(&mut window, "elder"); // removes the dialogue text
+/// ```
+pub struct Dialogue {
+    /// Pages still to be shown; the front page is the current one.
+    pages: VecDeque<Page>,
+
+    /// Number of characters of the current page revealed so far.
+    revealed: f32,
+
+    /// Time since the current page was fully revealed.
+    blink: f32,
+
+    blips: EffectStream,
+
+    /// Top-left corner of the dialogue, including speaker and portrait.
+    pub position: (usize, usize),
+
+    /// Size and layout rules of the text area.
+    pub text_box: TextBox,
+
+    /// Font scaling factor.
+    pub size: usize,
+
+    /// Default text color in 0xRRGGBB format.
+    pub color: u32,
+
+    /// Speaker name color in 0xRRGGBB format.
+    pub speaker_color: u32,
+
+    /// Characters revealed per second.
+    pub chars_per_second: f32,
+
+    /// Keys that skip to the end of a page or advance to the next one.
+    pub keys: Vec<Key>,
+
+    /// Sound played as characters are revealed, or `None` for silence.
+    ///
+    /// All blips of a dialogue share one audio stream, opened on the first
+    /// blip. Without an audio device, such as on CI runners, they are
+    /// skipped.
+    pub blip: Option<SoundSource>,
+
+    /// Number of revealed non-whitespace characters per blip.
+    ///
+    /// At most one blip plays per frame, however many characters appear.
+    pub blip_every: usize,
+
+    /// Text of the continue indicator.
+    pub indicator: String,
+}
+
+impl Dialogue {
+    /// Creates an empty dialogue at `position` with the given text area.
+    ///
+    /// Text is revealed at 30 characters per second, advanced with
+    /// [`Key::Enter`] or [`Key::Space`], and accompanied by
+    /// [`BuiltInSound::Blip`] for every character.
+    pub fn new(position: (usize, usize), text_box: TextBox) -> Self {
+        Self {
+            pages: VecDeque::new(),
+            revealed: 0.0,
+            blink: 0.0,
+            blips: EffectStream::new(),
+            position,
+            text_box,
+            size: 2,
+            color: 0xFFFFFF,
+            speaker_color: 0xFFFF00,
+            chars_per_second: 30.0,
+            keys: vec![Key::Enter, Key::Space],
+            blip: Some(SoundSource::BuiltIn(BuiltInSound::Blip)),
+            blip_every: 1,
+            indicator: ">".to_string(),
+        }
+    }
+
+    /// Adds a page to the end of the queue.
+    pub fn push(&mut self, page: Page) {
+        self.pages.push_back(page);
+    }
+
+    /// Returns the page currently shown, if any.
+    pub fn current(&self) -> Option<&Page> {
+        self.pages.front()
+    }
+
+    /// Returns `true` while there are pages left to show.
+    pub fn is_active(&self) -> bool {
+        !self.pages.is_empty()
+    }
+
+    /// Returns `true` if the current page is fully revealed.
+    pub fn is_page_complete(&self) -> bool {
+        self.pages
+            .front()
+            .is_none_or(|page| self.revealed as usize >= visible_chars(&page.text).len())
+    }
+
+    /// Reveals the rest of the current page at once.
+    pub fn skip(&mut self) {
+        if let Some(page) = self.pages.front() {
+            self.revealed = visible_chars(&page.text).len() as f32;
+        }
+    }
+
+    /// Ends the current page and starts revealing the next one.
+    ///
+    /// The portrait of the ended page is hidden.
+    pub fn next_page(&mut self, window: &mut Window) {
+        if let Some(portrait) = self.pages.pop_front().and_then(|page| page.portrait)
+            && let Some(sprite) = window.sprite_mut(portrait)
+        {
+            sprite.opacity = 0;
+        }
+        self.revealed = 0.0;
+        self.blink = 0.0;
+    }
+
+    /// Advances the dialogue by one frame.
+    ///
+    /// Reads [`Window::controls`] to skip or advance pages and reveals
+    /// characters according to the window's [`Clock`](crate::windows::Clock).
+    /// Call this once per frame after [`Window::update_controls`].
+    pub fn update(&mut self, window: &mut Window) {
+        let Some(page) = self.pages.front() else { return };
+
+        if self.keys.iter().any(|&key| window.controls.just_pressed(key)) {
+            if self.is_page_complete() {
+                self.next_page(window);
+            } else {
+                self.skip();
+            }
+            return;
+        }
+
+        let chars = visible_chars(&page.text);
+        let dt = window.clock.delta_secs();
+        let before = self.revealed as usize;
+        self.revealed = (self.revealed + self.chars_per_second * dt).min(chars.len() as f32);
+        let after = self.revealed as usize;
+
+        if let Some(blip) = &self.blip
+            && crosses_blip(&chars, before, after, self.blip_every)
+        {
+            self.blips.play(blip);
+        }
+
+        if after >= chars.len() {
+            self.blink += dt;
+        }
+    }
+
+    /// Draws the current page to the given window.
+    ///
+    /// Any previously drawn text using the same `id_prefix` is removed
+    /// first, so calling this after the last page has ended clears the
+    /// dialogue from the window.
+    ///
+    /// # Parameters
+    /// - `window`: The window to render into.
+    /// - `id_prefix`: A unique identifier prefix used for text elements.
+    ///
+    /// # Notes
+    /// - The current page's portrait is moved to the dialogue position,
+    ///   placed on the HUD layer and made opaque. Portraits of queued pages
+    ///   are hidden until their page is shown.
+    /// - Portrait sprites are taken over for good: their position, layer
+    ///   and opacity are not restored, and a portrait is left hidden once
+    ///   its page ends. Use sprites dedicated to the dialogue.
+    pub fn draw(&self, window: &mut Window, id_prefix: &str) {
+        let text_id = format!("{}_text", id_prefix);
+        let speaker_id = format!("{}_speaker", id_prefix);
+        let indicator_id = format!("{}_indicator", id_prefix);
+        window.remove_text(&text_id);
+        window.remove_text(&speaker_id);
+        window.remove_text(&indicator_id);
+
+        let Some(page) = self.pages.front() else { return };
+        let (mut x, mut y) = self.position;
+
+        // --- Portraits ---
+        for queued in self.pages.iter().skip(1) {
+            if let Some(sprite) = queued.portrait.and_then(|id| window.sprite_mut(id))
+                && queued.portrait != page.portrait
+            {
+                sprite.opacity = 0;
+            }
+        }
+        if let Some(sprite) = page.portrait.and_then(|id| window.sprite_mut(id)) {
+            sprite.position = (x as f32, y as f32);
+            sprite.opacity = 255;
+            sprite.layer = Layer::HUD;
+            x += sprite.size.0 + GAP;
+        }
+
+        // --- Speaker ---
+        if let Some(speaker) = &page.speaker {
+            window.show_text(&speaker_id, speaker, (x, y), self.size, self.speaker_color, TextAlign::Left);
+            y += measure_text(speaker, self.size).1 + GAP;
+        }
+
+        // --- Text ---
+        let mut text_box = self.text_box;
+        text_box.reveal = Some(self.revealed as usize);
+        window.show_text_box(&text_id, &page.text, (x, y), self.size, self.color, text_box);
+
+        // --- Continue indicator ---
+        if self.is_page_complete() && ((self.blink / BLINK_INTERVAL) as u32).is_multiple_of(2) {
+            let (_, height) = measure_text(&self.indicator, self.size);
+            window.show_text(
+                &indicator_id,
+                &self.indicator,
+                (x + text_box.width, (y + text_box.height).saturating_sub(height)),
+                self.size,
+                self.color,
+                TextAlign::Right,
+            );
+        }
+    }
+}
+
+/// Returns `true` if revealing `chars[before..after]` completes another
+/// group of `every` non-whitespace characters.
+fn crosses_blip(chars: &[char], before: usize, after: usize, every: usize) -> bool {
+    let every = every.max(1);
+    let spoken = |end: usize| chars[..end.min(chars.len())].iter().filter(|c| !c.is_whitespace()).count() / every;
+    after > before && spoken(after) > spoken(before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blips_skip_whitespace_and_are_throttled() {
+        let chars: Vec<char> = "ab  cd e".chars().collect();
+        let blips: Vec<usize> = (0..chars.len()).filter(|&i| crosses_blip(&chars, i, i + 1, 2)).collect();
+        assert_eq!(blips, [1, 5]);
+
+        // One blip per frame, however many characters appear.
+        assert!(crosses_blip(&chars, 0, chars.len(), 2));
+        assert!(!crosses_blip(&chars, 2, 4, 1));
+        assert!(!crosses_blip(&chars, 3, 3, 1));
+    }
+
+    #[test]
+    fn dialogue_blips_every_character_by_default() {
+        let dialogue = Dialogue::new((0, 0), TextBox::new(100, 20));
+        assert!(dialogue.blip.is_some());
+
+        let chars: Vec<char> = "ab c".chars().collect();
+        let blips: Vec<usize> = (0..chars.len())
+            .filter(|&i| crosses_blip(&chars, i, i + 1, dialogue.blip_every))
+            .collect();
+        assert_eq!(blips, [0, 1, 3]);
+    }
+
+    /// A silent dialogue with one page that wraps into several lines.
+    fn wrapped_dialogue() -> Dialogue {
+        let mut dialogue = Dialogue::new((0, 0), TextBox::new(60, 60));
+        dialogue.blip = None;
+        dialogue.size = 1;
+        dialogue.indicator = String::new();
+        dialogue.push(Page::new("ab cd ef\ngh ij"));
+        dialogue
+    }
+
+    #[test]
+    fn wrapped_page_completes_when_fully_drawn() {
+        let mut window = Window::headless(160, 80);
+
+        // Reference: the page fully shown.
+        let mut dialogue = wrapped_dialogue();
+        dialogue.skip();
+        dialogue.draw(&mut window, "npc");
+        window.draw();
+        let full = window.frame().to_vec();
+        assert!(window.text_line_count("npc_text").unwrap() > 2);
+
+        let mut dialogue = wrapped_dialogue();
+
+        for _ in 0..120 {
+            window.update_controls();
+            dialogue.update(&mut window);
+            dialogue.draw(&mut window, "npc");
+            window.draw();
+            assert_eq!(window.frame() == full.as_slice(), dialogue.is_page_complete());
+        }
+        assert!(dialogue.is_page_complete());
+    }
+}
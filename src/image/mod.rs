@@ -1,14 +1,16 @@
-use image::{GenericImageView, Pixel};
+use image::error::{ImageError, ParameterError, ParameterErrorKind};
+use image::{GenericImageView, Pixel, Rgb, RgbImage};
 use std::path::Path;
 
 
 /// Load an image file into a 2D bitmap buffer.
 ///
 /// The image is loaded using the `image` crate and converted into
-/// a `Vec<Vec<u32>>` where each pixel is stored in ARGB format.
+/// a `Vec<Vec<u32>>` where each pixel is stored in `0xAARRGGBB` format.
+/// Images without an alpha channel are loaded as fully opaque.
 ///
-/// # Panics
-/// Panics if the image cannot be opened or decoded.
+/// # Errors
+/// Returns an `image::ImageError` if the image cannot be opened or decoded.
 pub fn load_image_2d<P: AsRef<Path>>(path: P) -> image::ImageResult<Vec<Vec<u32>>> {
     let img = image::open(path)?;
     let (width, height) = img.dimensions();
@@ -17,10 +19,11 @@ pub fn load_image_2d<P: AsRef<Path>>(path: P) -> image::ImageResult<Vec<Vec<u32>
 
     for y in 0..height {
         for x in 0..width {
-            let pixel = img.get_pixel(x, y).to_rgb();
-            let [r, g, b] = pixel.0;
+            let pixel = img.get_pixel(x, y).to_rgba();
+            let [r, g, b, a] = pixel.0;
 
             buffer[y as usize][x as usize] =
+                ((a as u32) << 24) |
                 ((r as u32) << 16) |
                 ((g as u32) << 8)  |
                 (b as u32);
@@ -37,7 +40,7 @@ pub fn load_image_2d<P: AsRef<Path>>(path: P) -> image::ImageResult<Vec<Vec<u32>
 /// top-to-bottom, and returned as a flat vector.
 ///
 /// Each sprite is represented as a 2D pixel buffer (`Vec<Vec<u32>>`) in
-/// row-major order. Pixel values are encoded as `0xRRGGBB`.
+/// row-major order. Pixel values are encoded as `0xAARRGGBB`.
 ///
 /// # Parameters
 /// - `path`: Path to the sprite sheet image file.
@@ -56,12 +59,16 @@ pub fn load_image_2d<P: AsRef<Path>>(path: P) -> image::ImageResult<Vec<Vec<u32>
 /// # Notes
 /// - If the image dimensions are not evenly divisible by `sprite_width` or
 ///   `sprite_height`, any leftover pixels on the right or bottom edges are ignored.
-/// - Alpha channels are discarded; only RGB data is used.
+/// - Alpha channels are preserved; images without alpha are loaded as opaque.
 ///
 /// # Example
 /// ```no_run
+/// # use carose::image::load_sprite_sheet;
+/// # fn main() -> image::ImageResult<()> {
 /// let sprites = load_sprite_sheet("sprites.png", 32, 64)?;
 /// let first_sprite = &sprites[0];
+/// # Ok(())
+/// # }
 /// ```
 pub fn load_sprite_sheet<P: AsRef<Path>>(
     path: P,
@@ -85,10 +92,11 @@ pub fn load_sprite_sheet<P: AsRef<Path>>(
                     let px = sx * sprite_width + x;
                     let py = sy * sprite_height + y;
 
-                    let pixel = img.get_pixel(px, py).to_rgb();
-                    let [r, g, b] = pixel.0;
+                    let pixel = img.get_pixel(px, py).to_rgba();
+                    let [r, g, b, a] = pixel.0;
 
                     sprite[y as usize][x as usize] =
+                        ((a as u32) << 24) |
                         ((r as u32) << 16) |
                         ((g as u32) << 8)  |
                         (b as u32);
@@ -102,3 +110,45 @@ pub fn load_sprite_sheet<P: AsRef<Path>>(
     Ok(sprites)
 }
 
+
+/// Saves a flat pixel buffer to an image file.
+///
+/// The buffer is interpreted as `width * height` pixels in row-major order,
+/// encoded as `0xRRGGBB`. Any alpha byte is ignored. The output format is
+/// chosen from the file extension (for example `.png` or `.bmp`).
+///
+/// # Parameters
+/// - `path`: Destination file path.
+/// - `pixels`: Pixel buffer in row-major order.
+/// - `width`: Width of the image in pixels.
+/// - `height`: Height of the image in pixels.
+///
+/// # Errors
+/// Returns an `image::ImageError` if the image is empty, the buffer length
+/// does not match `width * height`, the extension is not a supported format, or the file
+/// cannot be written.
+pub fn save_image<P: AsRef<Path>>(
+    path: P,
+    pixels: &[u32],
+    width: usize,
+    height: usize,
+) -> image::ImageResult<()> {
+    if width == 0 || height == 0 || pixels.len() != width * height {
+        return Err(ImageError::Parameter(ParameterError::from_kind(
+            ParameterErrorKind::DimensionMismatch,
+        )));
+    }
+
+    let mut img = RgbImage::new(width as u32, height as u32);
+
+    for (i, pixel) in img.pixels_mut().enumerate() {
+        let color = pixels[i];
+        *pixel = Rgb([
+            ((color >> 16) & 0xFF) as u8,
+            ((color >> 8) & 0xFF) as u8,
+            (color & 0xFF) as u8,
+        ]);
+    }
+
+    img.save(path)
+}
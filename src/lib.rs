@@ -28,9 +28,15 @@
 //!
 //! Most interaction happens through methods on `Window`.
 //!
+//! Finished frames are handed to a presentation [`windows::Backend`].
+//! [`Window::new`] opens a native window, while [`Window::headless`]
+//! renders into memory so game loops can run without a display.
+//!
 //! ### Sprites
 //!
-//! Sprites are stored internally by the window and can be:
+//! Sprites are stored internally by the window and addressed through
+//! stable [`sprites::SpriteId`] handles that never silently point at a
+//! different sprite after removals. Sprites can be:
 //! - Solid color rectangles
 //! - Bitmap sprites
 //! - Animated bitmap sprites
@@ -40,11 +46,37 @@
 //! - Health
 //! - Collision checks
 //! - Velocity and acceleration vectors
+//! - Alpha-blended bitmaps and per-sprite opacity
 //!
 //! ### Text
 //!
-//! Text is rendered using a built-in 5×5 bitmap font and can be aligned
-//! automatically or manually using [`TextAlign`].
+//! Text is rendered using a built-in 5×5 bitmap font covering printable
+//! ASCII, with a box glyph for anything unsupported. It can be aligned
+//! left, centered, right, or shrunk to fit using [`TextAlign`].
+//! [`Window::measure_text`] returns the exact pixel size of a string.
+//!
+//! Other bitmap fonts can be loaded as a [`Font`] from BMFont descriptors
+//! or monospaced glyph sheets, registered with [`Window::add_font`] and
+//! selected per text item.
+//!
+//! [`Window::show_text_box`] lays out multi-line text inside a rectangle
+//! with word wrapping, `\n` line breaks, inline
+//! `[color=#rrggbb]...[/color]` markup and a clip, scroll or ellipsis
+//! overflow policy.
+//!
+//! A [`windows::text::TextStyle`] adds an outline, a drop shadow or a
+//! translucent background panel to any text item.
+//!
+//! ### Layers
+//!
+//! Sprites and text are drawn in [`Layer`] order (background, world, HUD,
+//! overlay), then by z-index. Remaining ties draw sprites first, in the
+//! order they were added, then text in order of its id. Sprites default to
+//! the world layer and text to the HUD layer.
+//!
+//! Layers below the HUD are in world space and are drawn through the
+//! window's [`windows::Camera`], which supports following a sprite with a
+//! deadzone, level bounds, integer zoom and screen shake.
 //!
 //! ### Menus
 //!
@@ -52,6 +84,19 @@
 //! and centered rendering, intended for pause menus, main menus, and game-over
 //! screens.
 //!
+//! ### Dialogue
+//!
+//! [`Dialogue`] shows queued pages of text with a typewriter effect, an
+//! optional speaker name and portrait, a blinking continue indicator, and
+//! a blip sound per character.
+//!
+//! ### Text Input
+//!
+//! [`TextInput`] is a single-line entry field for names and save slots,
+//! with a cursor, backspace, a maximum length, a configurable character
+//! set and submit on Enter. It reads the typed-character stream from
+//! [`controls::Input::typed_chars`].
+//!
 //! ### Audio
 //!
 //! The audio module provides:
@@ -61,12 +106,47 @@
 //! ### Controls
 //!
 //! The controls system tracks keyboard and mouse input with support for:
-//! - Pressed (held)
+//! - Pressed (held) and held duration
 //! - Released
+//! - Just pressed / just released (edges within the current frame)
+//! - Repeated (auto-repeat with a configurable delay and rate)
 //! - Clicked (press → release)
 //!
 //! Input is frame-based and updated manually each loop.
 //!
+//! By default input comes from the window's own key, mouse-button, cursor
+//! and scroll events. [`Window::with_input`] with
+//! [`controls::InputBackend::Native`] reads raw OS devices instead, such as
+//! `/dev/input` on Linux.
+//!
+//! [`Window::mouse_position`] and [`Window::mouse_world_position`] give the
+//! cursor in frame and world coordinates, and [`Window::hovered_sprite`] and
+//! [`Window::clicked_sprite`] pick the topmost sprite under it.
+//!
+//! [`Window::enable_gamepads`] adds gamepads read through evdev, exposing
+//! buttons in a standard layout and analog axes with per-axis deadzones
+//! through [`controls::Input::gamepad`].
+//!
+//! [`controls::ActionMap`] maps named actions and axes such as `"jump"` or
+//! `"move_x"` to any number of keys and gamepad inputs, supports rebinding
+//! at runtime, and saves and loads bindings as a config file.
+//!
+//! [`Window::start_recording`] captures every frame of input into a
+//! compact [`controls::InputRecording`], and [`Window::play_recording`]
+//! feeds it back in place of live devices with the recorded frame times.
+//! Together with a seeded RNG this replays a session exactly, also on a
+//! headless window.
+//!
+//! Tests drive input without devices through [`Window::inject`] or a
+//! frame-by-frame [`testing::InputScript`]; [`controls::InputBackend::Scripted`]
+//! shuts out real devices on a native window.
+//!
+//! ### Timing
+//!
+//! Every window has a [`windows::Clock`] exposing delta time, elapsed time
+//! and measured FPS. [`Window::run`] drives a [`windows::Game`] at a fixed
+//! timestep, independent of the frame rate.
+//!
 //! ## Typical Usage
 //!
 //! A typical game loop looks like:
@@ -92,11 +172,14 @@
 //!
 //! - [`windows`] — Window creation, rendering, text, sprites
 //! - [`sprites`] — Sprite types, rendering, animation, physics vectors
-//! - [`controls`] — Keyboard and mouse input handling
+//! - [`controls`] — Keyboard, mouse and gamepad input handling
 // ! - [`menu`] — Menu UI utilities
+//! - [`dialogue`] — Typewriter dialogue boxes
+//! - [`text_input`] — Editable text fields
 //! - [`audio`] — Sound effects and background music
 //! - [`image`] — Bitmap and sprite sheet loading helpers
 //! - [`colors`] — Common color constants
+//! - [`testing`] — Frame and input scripting, golden-image snapshot comparison
 //!
 //! This crate is intended for small to mid-sized 2D games,
 //! prototypes, and learning projects.
@@ -104,13 +187,20 @@
 
 pub mod windows;
 pub mod menu;
+pub mod dialogue;
+pub mod text_input;
 pub mod image;
 pub mod colors;
 pub mod sprites;
 pub mod audio;
 pub mod controls;
+pub mod testing;
 pub use windows::Window;
 pub use menu::Menu;
+pub use dialogue::Dialogue;
+pub use text_input::TextInput;
 pub use windows::text::TextAlign;
+pub use windows::font::Font;
+pub use windows::Layer;
 
 
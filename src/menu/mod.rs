@@ -1,4 +1,5 @@
 use crate::{Window, TextAlign};
+use crate::controls::Key;
 
 /// A simple vertical menu for selectable text-based options.
 ///
@@ -63,6 +64,23 @@ impl Menu {
         self.options[self.selected]
     }
 
+    /// Handles keyboard navigation for the current frame.
+    ///
+    /// [`Key::Up`] / `W` and [`Key::Down`] / `S` move the selection and
+    /// auto-repeat while held. Returns the selected option on the frame
+    /// [`Key::Enter`] is pressed, and `None` otherwise. Call this once per
+    /// frame after [`Window::update_controls`].
+    pub fn update(&mut self, window: &Window) -> Option<&'static str> {
+        let controls = &window.controls;
+        if controls.repeated(Key::Up) || controls.repeated(Key::Char('w')) {
+            self.move_up();
+        }
+        if controls.repeated(Key::Down) || controls.repeated(Key::Char('s')) {
+            self.move_down();
+        }
+        controls.just_pressed(Key::Enter).then(|| self.options[self.selected])
+    }
+
     /// Draws the menu to the given window.
     ///
     /// All menu options are rendered vertically centered with a fixed
@@ -77,7 +95,6 @@ impl Menu {
     /// - `id_prefix`: A unique identifier prefix used for text elements.
     ///
     /// # Notes
-    /// - Text width is estimated for centering using a fixed font scale.
     /// - Text is horizontally centered in the window.
     pub fn draw(&self, window: &mut Window, id_prefix: &str) {
         // Remove previous text for this menu
@@ -95,17 +112,15 @@ impl Menu {
 
         for (i, option) in self.options.iter().enumerate() {
             let color = if i == self.selected { self.selected_col } else { self.unselected };
-            let text_width = option.len() * 5 * 5; // rough width approximation for AutoFit 5x5 font scaled by size
-            let x = width / 2 - text_width / 2;    // center text horizontally
             let y = start_y + i * gap;
 
             window.show_text(
                 &format!("{}_{}", id_prefix, i),
                 option,
-                (x, y),
+                (width / 2, y),
                 5,
                 color,
-                TextAlign::AutoFit,
+                TextAlign::Center,
             );
         }
     }
@@ -3,7 +3,10 @@ use crate::image::{
     load_image_2d
 };
 use crate::Window;
+use crate::windows::Layer;
 mod vectors;
+mod store;
+pub use store::{SpriteId, SpriteStore};
 
 
 
@@ -11,17 +14,18 @@ mod vectors;
 ///
 /// Vectors are evaluated by the engine to update sprite movement
 /// and physics-like behavior.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Vector {
     /// Constant velocity applied every update tick.
     ///
-    /// Values represent `(dx, dy)` in pixels per frame.
-    Velocity(i32, i32),
+    /// Values represent `(dx, dy)` in pixels per frame and may be
+    /// fractional, e.g. `0.5` moves one pixel every two frames.
+    Velocity(f32, f32),
 
     /// Acceleration applied to velocity each update tick.
     ///
     /// Values represent `(ax, ay)` in pixels per frame².
-    Acceleration(i32, i32),
+    Acceleration(f32, f32),
 }
 
 
@@ -38,8 +42,9 @@ pub enum SpriteRender {
 
     /// Static bitmap sprite.
     ///
-    /// Pixels are stored in row-major order.
-    /// A value of `0` is treated as transparent.
+    /// Pixels are stored in row-major order as `0xAARRGGBB` and are
+    /// alpha-blended over whatever is behind them. Pixels with an alpha
+    /// of `0` (including the value `0`) are fully transparent.
     Bitmap {
         /// 2D pixel buffer: rows → columns.
         pixels: Vec<Vec<u32>>,
@@ -48,6 +53,7 @@ pub enum SpriteRender {
     /// Animated bitmap sprite.
     ///
     /// Frames are cycled automatically using a fixed frame delay.
+    /// Pixels are blended the same way as [`SpriteRender::Bitmap`].
     AnimatedBitmap {
         /// Animation frames stored as 2D pixel buffers.
         frames: Vec<Vec<Vec<u32>>>,
@@ -106,8 +112,12 @@ pub struct Sprite {
     /// When health reaches zero or below, the sprite is considered dead.
     pub health: i32,
 
-    /// Top-left position in screen coordinates.
-    pub position: (usize, usize),
+    /// Top-left position in world coordinates.
+    ///
+    /// Positions are signed and sub-pixel: sprites may sit partially or
+    /// fully off the left/top edge, and are drawn at the floor of their
+    /// coordinates.
+    pub position: (f32, f32),
 
     /// Logical size of the sprite in pixels.
     pub size: (usize, usize),
@@ -122,12 +132,85 @@ pub struct Sprite {
     ///
     /// Includes velocity and acceleration components.
     pub vectors: Vec<Vector>,
+
+    /// Opacity multiplier applied when drawing, from `0` (invisible)
+    /// to `255` (fully opaque).
+    ///
+    /// Useful for fading sprites in and out.
+    pub opacity: u8,
+
+    /// Render layer the sprite is drawn in.
+    ///
+    /// Defaults to [`Layer::WORLD`].
+    pub layer: Layer,
+
+    /// Draw order within the layer; higher values are drawn on top.
+    pub z_index: i32,
 }
 
 
 
 
 impl Sprite {
+    /// Returns `true` if the bounding boxes of two sprites intersect.
+    pub fn overlaps(&self, other: &Sprite) -> bool {
+        let (x1, y1) = self.position;
+        let (w1, h1) = (self.size.0 as f32, self.size.1 as f32);
+        let (x2, y2) = other.position;
+        let (w2, h2) = (other.size.0 as f32, other.size.1 as f32);
+
+        x1 < x2 + w2 &&
+        x1 + w1 > x2 &&
+        y1 < y2 + h2 &&
+        y1 + h1 > y2
+    }
+
+    /// Returns `true` if `point` lies on a visible pixel of the sprite.
+    ///
+    /// `point` is in the same coordinates as [`Sprite::position`]. Fully
+    /// transparent sprites never contain a point, and bitmap sprites only
+    /// contain points over pixels with a non-zero alpha.
+    pub fn contains_point(&self, point: (f32, f32)) -> bool {
+        let x = point.0 - self.position.0;
+        let y = point.1 - self.position.1;
+        if self.opacity == 0 || x < 0.0 || y < 0.0 {
+            return false;
+        }
+        let (x, y) = (x as usize, y as usize);
+
+        let pixels = match &self.render {
+            SpriteRender::Color(_) => return x < self.size.0 && y < self.size.1,
+            SpriteRender::Bitmap { pixels } => pixels,
+            SpriteRender::AnimatedBitmap { frames, frame_index, .. } => {
+                let Some(frame) = frames.get(*frame_index) else { return false };
+                frame
+            }
+        };
+        pixels
+            .get(y)
+            .and_then(|row| row.get(x))
+            .is_some_and(|pixel| pixel >> 24 != 0)
+    }
+
+    /// Returns `true` if the sprite lies completely outside a
+    /// `width` x `height` area anchored at the origin.
+    ///
+    /// This ignores the camera; [`Window::remove_if_out_of_screen`] and
+    /// related helpers test against what the camera actually shows.
+    pub fn is_offscreen(&self, width: usize, height: usize) -> bool {
+        self.is_outside((0.0, 0.0, width as f32, height as f32))
+    }
+
+    /// Returns `true` if the sprite lies completely outside the rectangle
+    /// `(x, y, width, height)`.
+    pub(crate) fn is_outside(&self, area: (f32, f32, f32, f32)) -> bool {
+        let (ax, ay, aw, ah) = area;
+        let (x, y) = self.position;
+        let (w, h) = (self.size.0 as f32, self.size.1 as f32);
+
+        x + w <= ax || x >= ax + aw || y + h <= ay || y >= ay + ah
+    }
+
     // Multiply size by a defined amount
     pub fn upscale(&mut self, factor: usize) {
         if factor <= 1 {
@@ -197,7 +280,7 @@ impl Sprite {
 
     /// Create a solid-color rectangular sprite.
     pub fn new_color(
-        position: (usize, usize),
+        position: (f32, f32),
         size: (usize, usize),
         sprite_type: SpriteType,
         health: i32,
@@ -212,14 +295,17 @@ impl Sprite {
             render: SpriteRender::Color(color),
             is_solid,
             vectors: Vec::new(),
+            opacity: 255,
+            layer: Layer::WORLD,
+            z_index: 0,
         }
     }
 
     /// Create a bitmap sprite from a 2D pixel buffer.
     ///
-    /// Pixels with value `0` are treated as transparent.
+    /// Pixels are `0xAARRGGBB`; an alpha of `0` is transparent.
     pub fn new_bitmap(
-        position: (usize, usize),
+        position: (f32, f32),
         sprite_type: SpriteType,
         health: i32,
         pixels: Vec<Vec<u32>>,
@@ -236,6 +322,9 @@ impl Sprite {
             render: SpriteRender::Bitmap { pixels },
             is_solid,
             vectors: Vec::new(),
+            opacity: 255,
+            layer: Layer::WORLD,
+            z_index: 0,
         }
     }
 
@@ -243,7 +332,7 @@ impl Sprite {
     ///
     /// All frames are assumed to be the same size.
     pub fn new_animated_bitmap(
-        position: (usize, usize),
+        position: (f32, f32),
         sprite_type: SpriteType,
         health: i32,
         frames: Vec<Vec<Vec<u32>>>,
@@ -271,12 +360,15 @@ impl Sprite {
             },
             is_solid,
             vectors: Vec::new(),
+            opacity: 255,
+            layer: Layer::WORLD,
+            z_index: 0,
         }
     }
 
     /// Create a wall sprite (solid, indestructible).
     pub fn new_wall(
-        position: (usize, usize),
+        position: (f32, f32),
         size: (usize, usize),
     ) -> Self {
         Sprite {
@@ -287,22 +379,46 @@ impl Sprite {
             render: SpriteRender::Color(0x555555),
             is_solid: true,
             vectors: Vec::new(),
+            opacity: 255,
+            layer: Layer::WORLD,
+            z_index: 0,
         }
     }
 }
 
 impl Window {
 
+    /// Add an already constructed sprite to the window.
+    ///
+    /// Returns the handle of the newly added sprite.
+    pub fn add_sprite(&mut self, sprite: Sprite) -> SpriteId {
+        self.sprites.insert(sprite)
+    }
+
+    /// Look up a sprite by handle.
+    ///
+    /// Returns `None` if the sprite has been removed.
+    pub fn sprite(&self, id: SpriteId) -> Option<&Sprite> {
+        self.sprites.get(id)
+    }
+
+    /// Look up a sprite by handle for modification.
+    ///
+    /// Returns `None` if the sprite has been removed.
+    pub fn sprite_mut(&mut self, id: SpriteId) -> Option<&mut Sprite> {
+        self.sprites.get_mut(id)
+    }
+
     /// Create a bitmap sprite from an image file.
     ///
     /// The image is loaded from disk and used as the sprite's pixel data.
-    /// Returns the index of the newly created sprite.
+    /// Returns the handle of the newly created sprite.
     pub fn create_bitmap_sprite_from_file(
         &mut self,
-        position: (usize, usize),
+        position: (f32, f32),
         path: &str,
         sprite_type: SpriteType,
-    ) -> usize {
+    ) -> SpriteId {
         let bitmap = load_image_2d(path).expect("Failed to load image");
         self.create_bitmap_sprite(position, bitmap, sprite_type)
     }
@@ -310,15 +426,15 @@ impl Window {
     /// Create an animated sprite from multiple image files.
     ///
     /// Each file represents a single animation frame.
-    /// Returns the index of the newly created sprite.
+    /// Returns the handle of the newly created sprite.
     pub fn create_animated_bitmap_sprite_from_files(
         &mut self,
-        position: (usize, usize),
+        position: (f32, f32),
         health: i32,
         paths: Vec<String>,
         sprite_type: SpriteType,
         frame_delay: u32,
-    ) -> usize {
+    ) -> SpriteId {
         let frames: Vec<Vec<Vec<u32>>> = paths
             .iter()
             .map(|path| load_image_2d(path).expect("Failed to load image"))
@@ -330,158 +446,98 @@ impl Window {
     /// Create an animated sprite from preloaded frames.
     ///
     /// Each frame is a 2D bitmap. All frames are assumed to be the same size.
-    /// Returns the index of the newly created sprite.
+    /// Returns the handle of the newly created sprite.
     pub fn create_animated_sprite(
         &mut self,
-        position: (usize, usize),
+        position: (f32, f32),
         size: (usize, usize),
         sprite_type: SpriteType,
         health: i32,
         frames: Vec<Vec<Vec<u32>>>,
         frame_delay: u32,
-    ) -> usize {
-        self.sprites.push(Sprite {
-            sprite_type,
-            health,
-            position,
-            size,
-            render: SpriteRender::AnimatedBitmap {
-                frames,
-                frame_index: 0,
-                frame_delay,
-                frame_timer: 0,
-            },
-            is_solid: false,
-            vectors: Vec::new(),
-        });
-        self.sprites.len() - 1
+    ) -> SpriteId {
+        let mut sprite = Sprite::new_animated_bitmap(position, sprite_type, health, frames, frame_delay, false);
+        sprite.size = size;
+        self.sprites.insert(sprite)
     }
 
     /// Create a solid-colored rectangular sprite.
     ///
     /// Useful for debug objects, simple entities, or placeholders.
-    /// Returns the index of the newly created sprite.
+    /// Returns the handle of the newly created sprite.
     pub fn create_colored_sprite(
         &mut self,
-        position: (usize, usize),
+        position: (f32, f32),
         size: (usize, usize),
         sprite_type: SpriteType,
         health: i32,
         color: u32,
-    ) -> usize {
-        self.sprites.push(
-            Sprite::new_color(position, size, sprite_type, health, color, false)
-        );
-
-        self.sprites.push(Sprite {
-            sprite_type,
-            health,
-            position,
-            size,
-            render: SpriteRender::Color(color),
-            is_solid: false,
-            vectors: vec![Vector::Velocity(0, 0)],
-        });
-        self.sprites.len() - 1
+    ) -> SpriteId {
+        let mut sprite = Sprite::new_color(position, size, sprite_type, health, color, false);
+        sprite.vectors.push(Vector::Velocity(0.0, 0.0));
+        self.sprites.insert(sprite)
     }
 
     /// Create a bitmap sprite from an already loaded 2D pixel buffer.
     ///
-    /// Pixels with value `0` are treated as transparent.
-    /// Returns the index of the newly created sprite.
+    /// Pixels are `0xAARRGGBB`; an alpha of `0` is transparent.
+    /// Returns the handle of the newly created sprite.
     pub fn create_bitmap_sprite(
         &mut self,
-        position: (usize, usize),
+        position: (f32, f32),
         bitmap: Vec<Vec<u32>>,
         sprite_type: SpriteType,
-    ) -> usize {
-        
-
-        self.sprites.push(
+    ) -> SpriteId {
+        self.sprites.insert(
             Sprite::new_bitmap(position, sprite_type, 1, bitmap, false)
-        );
-
-        self.sprites.len() - 1
+        )
     }
 
     /// Create an animated bitmap sprite from preloaded frames.
     ///
     /// Frames are advanced automatically using `frame_delay`.
-    /// Returns the index of the newly created sprite.
+    /// Returns the handle of the newly created sprite.
     pub fn create_animated_bitmap_sprite(
         &mut self,
-        position: (usize, usize),
+        position: (f32, f32),
         health: i32,
         bitmaps: Vec<Vec<Vec<u32>>>,
         sprite_type: SpriteType,
         frame_delay: u32,
-    ) -> usize {
-        self.sprites.push(
+    ) -> SpriteId {
+        self.sprites.insert(
             Sprite::new_animated_bitmap(position, sprite_type, health, bitmaps, frame_delay, false)
-        );
-
-        self.sprites.len() - 1
+        )
     }
 
     /// Create an animated sprite from a sprite sheet.
     ///
     /// The sprite sheet is sliced into frames using the provided
     /// width and height.
-    /// Returns the index of the newly created sprite.
+    /// Returns the handle of the newly created sprite.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_animated_sprite_from_sheet(
         &mut self,
-        position: (usize, usize),
+        position: (f32, f32),
         health: i32,
         sheet: &str,
         width: u32,
         height: u32,
         sprite_type: SpriteType,
         frame_delay: u32,
-    ) -> usize {
+    ) -> SpriteId {
         let bitmaps = load_sprite_sheet(sheet, width, height)
             .expect("Failed to load sprite frames from sheet");
 
-        let (width, height) = if let Some(frame) = bitmaps.first() {
-            let h = frame.len();
-            let w = if h > 0 { frame[0].len() } else { 0 };
-            (w, h)
-        } else {
-            (0, 0)
-        };
-
-        self.sprites.push(Sprite {
-            sprite_type,
-            health,
-            position,
-            size: (width, height),
-            render: SpriteRender::AnimatedBitmap {
-                frames: bitmaps,
-                frame_index: 0,
-                frame_delay,
-                frame_timer: 0,
-            },
-            is_solid: false,
-            vectors: Vec::new(),
-        });
-
-        self.sprites.len() - 1
+        self.create_animated_bitmap_sprite(position, health, bitmaps, sprite_type, frame_delay)
     }
 
     /// Create an indestructible wall sprite.
     ///
     /// Walls are solid and block movement.
-    /// Returns the index of the newly created sprite.
-    pub fn create_wall(&mut self, position: (usize, usize), size: (usize, usize)) -> usize {
-        self.sprites.push(Sprite {
-            sprite_type: SpriteType::Wall,
-            health: i32::MAX,
-            position,
-            size,
-            render: SpriteRender::Color(0x555555),
-            is_solid: true,
-            vectors: Vec::new(),
-        });
-        self.sprites.len() - 1
+    /// Returns the handle of the newly created sprite.
+    pub fn create_wall(&mut self, position: (f32, f32), size: (usize, usize)) -> SpriteId {
+        self.sprites.insert(Sprite::new_wall(position, size))
     }
 
     /// Advance the animation state of an animated sprite render.
@@ -501,34 +557,54 @@ impl Window {
     }
 
     /// Move a sprite to a new position.
-    pub fn move_sprite(&mut self, index: usize, new_pos: (usize, usize)) {
-        if let Some(sprite) = self.sprites.get_mut(index) {
+    ///
+    /// Does nothing if the sprite has been removed.
+    pub fn move_sprite(&mut self, id: SpriteId, new_pos: (f32, f32)) {
+        if let Some(sprite) = self.sprites.get_mut(id) {
             sprite.position = new_pos;
         }
     }
 
-    /// Remove a sprite by index.
-    pub fn remove_sprite(&mut self, index: usize) {
-        if index < self.sprites.len() {
-            self.sprites.remove(index);
+    /// Set the render layer and z-index of a sprite.
+    ///
+    /// Does nothing if the sprite has been removed.
+    pub fn set_sprite_layer(&mut self, id: SpriteId, layer: Layer, z_index: i32) {
+        if let Some(sprite) = self.sprites.get_mut(id) {
+            sprite.layer = layer;
+            sprite.z_index = z_index;
         }
     }
 
+    /// Remove a sprite by handle.
+    ///
+    /// Returns the removed sprite, or `None` if it was already removed.
+    pub fn remove_sprite(&mut self, id: SpriteId) -> Option<Sprite> {
+        self.sprites.remove(id)
+    }
+
     /// Invoke a callback for each sprite of a given type that has died.
+    ///
+    /// The callback may freely add or remove sprites.
     pub fn on_death<F>(&mut self, sprite_type: SpriteType, mut on_death: F)
     where
-        F: FnMut(&mut Window, usize),
+        F: FnMut(&mut Window, SpriteId),
     {
-        for i in 0..self.sprites.len() {
-            if self.sprites[i].sprite_type == sprite_type
-                && self.sprites[i].health <= 0
-            {
-                on_death(self, i);
+        let dead: Vec<SpriteId> = self.sprites.iter()
+            .filter(|(_, s)| s.sprite_type == sprite_type && s.health <= 0)
+            .map(|(id, _)| id)
+            .collect();
+
+        for id in dead {
+            if self.sprites.contains(id) {
+                on_death(self, id);
             }
         }
     }
 
     /// Invoke a callback when two sprite types collide.
+    ///
+    /// The callback may freely add or remove sprites; pairs involving
+    /// a removed sprite are skipped.
     pub fn on_collision<F>(
         &mut self,
         a_type: SpriteType,
@@ -536,32 +612,20 @@ impl Window {
         mut on_collision: F,
     )
     where
-        F: FnMut(&mut Window, usize, usize),
+        F: FnMut(&mut Window, SpriteId, SpriteId),
     {
-        let len = self.sprites.len();
-
-        for i in 0..len {
-            if self.sprites[i].sprite_type != a_type {
-                continue;
-            }
+        let ids: Vec<SpriteId> = self.sprites.ids().to_vec();
 
-            let (x1, y1) = self.sprites[i].position;
-            let (w1, h1) = self.sprites[i].size;
+        for &a in &ids {
+            for &b in &ids {
+                if a == b { continue; }
 
-            for j in 0..len {
-                if i == j || self.sprites[j].sprite_type != b_type {
+                let (Some(sa), Some(sb)) = (self.sprites.get(a), self.sprites.get(b)) else {
                     continue;
-                }
-
-                let (x2, y2) = self.sprites[j].position;
-                let (w2, h2) = self.sprites[j].size;
+                };
 
-                if x1 < x2 + w2 &&
-                   x1 + w1 > x2 &&
-                   y1 < y2 + h2 &&
-                   y1 + h1 > y2
-                {
-                    on_collision(self, i, j);
+                if sa.sprite_type == a_type && sb.sprite_type == b_type && sa.overlaps(sb) {
+                    on_collision(self, a, b);
                 }
             }
         }
@@ -574,27 +638,16 @@ impl Window {
         collider_type: SpriteType,
         health: i32,
     ) {
-        let len = self.sprites.len();
+        let sprites = self.sprites.values_mut();
+        let len = sprites.len();
         for i in 0..len {
-            if self.sprites[i].sprite_type != target_type { continue; }
+            if sprites[i].sprite_type != target_type { continue; }
 
             for j in 0..len {
-                if i == j || self.sprites[j].sprite_type != collider_type { continue; }
-
-                let (s1, s2) = if i < j {
-                    let (left, right) = self.sprites.split_at_mut(j);
-                    (&mut left[i], &mut right[0])
-                } else {
-                    let (left, right) = self.sprites.split_at_mut(i);
-                    (&mut right[0], &mut left[j])
-                };
+                if i == j || sprites[j].sprite_type != collider_type { continue; }
 
-                if s1.position.0 < s2.position.0 + s2.size.0
-                    && s1.position.0 + s1.size.0 > s2.position.0
-                    && s1.position.1 < s2.position.1 + s2.size.1
-                    && s1.position.1 + s1.size.1 > s2.position.1
-                {
-                    s1.health = s1.health.saturating_add(health);
+                if sprites[i].overlaps(&sprites[j]) {
+                    sprites[i].health = sprites[i].health.saturating_add(health);
                 }
             }
         }
@@ -602,16 +655,9 @@ impl Window {
 
     /// Remove all dead sprites of a given type.
     pub fn remove_on_death(&mut self, sprite_type: SpriteType) {
-        let mut dead_indices = Vec::new();
-        for (i, sprite) in self.sprites.iter().enumerate() {
-            if sprite.sprite_type == sprite_type && sprite.health <= 0 {
-                dead_indices.push(i);
-            }
-        }
-
-        for &i in dead_indices.iter().rev() {
-            self.remove_sprite(i);
-        }
+        self.sprites.retain(|_, sprite| {
+            !(sprite.sprite_type == sprite_type && sprite.health <= 0)
+        });
     }
 
     /// Remove sprites of a given type when they collide with another type.
@@ -620,75 +666,47 @@ impl Window {
         collider_type: SpriteType,
         remove_type: SpriteType,
     ) {
-        let mut dead_indices = Vec::new();
-        let len = self.sprites.len();
-
-        for i in 0..len {
-            if self.sprites[i].sprite_type != remove_type {
-                continue;
-            }
-
-            let (x1, y1) = self.sprites[i].position;
-            let (w1, h1) = self.sprites[i].size;
-
-            for j in 0..len {
-                if i == j || self.sprites[j].sprite_type != collider_type {
-                    continue;
-                }
-
-                let (x2, y2) = self.sprites[j].position;
-                let (w2, h2) = self.sprites[j].size;
-
-                if x1 < x2 + w2 &&
-                   x1 + w1 > x2 &&
-                   y1 < y2 + h2 &&
-                   y1 + h1 > y2
-                {
-                    dead_indices.push(i);
-                    break;
-                }
-            }
-        }
-
-        for &i in dead_indices.iter().rev() {
-            self.remove_sprite(i);
-        }
+        let sprites = self.sprites.values();
+        let hit: Vec<bool> = sprites.iter().enumerate().map(|(i, sprite)| {
+            sprite.sprite_type == remove_type
+                && sprites.iter().enumerate().any(|(j, other)| {
+                    i != j && other.sprite_type == collider_type && sprite.overlaps(other)
+                })
+        }).collect();
+
+        let mut hit = hit.into_iter();
+        self.sprites.retain(|_, _| !hit.next().unwrap_or(false));
     }
 
     /// Remove sprites that are completely outside the screen bounds.
+    ///
+    /// Sprites in world-space layers are tested against the area the
+    /// [`Camera`](crate::windows::Camera) shows, not the world origin.
     pub fn remove_if_out_of_screen(&mut self, sprite_type: SpriteType) {
-        let mut dead_indices = Vec::new();
-
-        for (i, sprite) in self.sprites.iter().enumerate() {
-            if sprite.sprite_type != sprite_type { continue; }
-
-            let x = sprite.position.0 as i32;
-            let y = sprite.position.1 as i32;
-            let w = sprite.size.0 as i32;
-            let h = sprite.size.1 as i32;
-
-            if x + w <= 0 || x >= self.width as i32
-                || y + h <= 0 || y >= self.height as i32
-            {
-                dead_indices.push(i);
-            }
-        }
-
-        for &i in dead_indices.iter().rev() {
-            self.remove_sprite(i);
-        }
+        let camera = &self.camera;
+        let (width, height) = (self.width, self.height);
+        self.sprites.retain(|_, sprite| {
+            !(sprite.sprite_type == sprite_type
+                && sprite.is_outside(camera.visible_area(sprite.layer, width, height)))
+        });
     }
 
     /// Clamp sprites of a given type so they remain inside the screen.
+    ///
+    /// Sprites in world-space layers are kept inside the area the
+    /// [`Camera`](crate::windows::Camera) shows.
     pub fn prevent_leaving_screen(&mut self, sprite_type: SpriteType) {
-        for sprite in self.sprites.iter_mut() {
+        let camera = &self.camera;
+        let (width, height) = (self.width, self.height);
+        for sprite in self.sprites.values_mut() {
             if sprite.sprite_type != sprite_type { continue; }
 
-            let (w, h) = sprite.size;
+            let (ax, ay, aw, ah) = camera.visible_area(sprite.layer, width, height);
+            let (w, h) = (sprite.size.0 as f32, sprite.size.1 as f32);
             let (x, y) = sprite.position;
 
-            let new_x = x.clamp(0, self.width.saturating_sub(w));
-            let new_y = y.clamp(0, self.height.saturating_sub(h));
+            let new_x = x.clamp(ax, ax + (aw - w).max(0.0));
+            let new_y = y.clamp(ay, ay + (ah - h).max(0.0));
 
             sprite.position = (new_x, new_y);
         }
@@ -701,7 +719,7 @@ impl Window {
         score: &mut i32,
         points: i32,
     ) {
-        for sprite in self.sprites.iter_mut() {
+        for sprite in self.sprites.values_mut() {
             if sprite.sprite_type != sprite_type {
                 continue;
             }
@@ -714,19 +732,65 @@ impl Window {
     }
 
     /// Change sprite health when fully outside the screen.
+    ///
+    /// Sprites in world-space layers are tested against the area the
+    /// [`Camera`](crate::windows::Camera) shows.
     pub fn change_health_offscreen(&mut self, sprite_type: SpriteType, health_change: i32) {
-        let (width, height) = self.get_size();
-        for sprite in &mut self.sprites {
+        let camera = &self.camera;
+        let (width, height) = (self.width, self.height);
+        for sprite in self.sprites.values_mut() {
             if sprite.sprite_type != sprite_type {
                 continue;
             }
 
-            let (x, y) = sprite.position;
-            let (w, h) = sprite.size;
-
-            if x + w <= 0 || x >= width || y + h <= 0 || y >= height {
+            if sprite.is_outside(camera.visible_area(sprite.layer, width, height)) {
                 sprite.health = sprite.health.saturating_add(health_change);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(position: (f32, f32), sprite_type: SpriteType) -> Sprite {
+        Sprite::new_color(position, (10, 10), sprite_type, 10, 0xFFFFFF, false)
+    }
+
+    /// A 100x100 window scrolled so the camera shows world `(200..300, 0..100)`.
+    fn scrolled_window() -> Window {
+        let mut window = Window::headless(100, 100);
+        window.camera.position = (200.0, 0.0);
+        window
+    }
+
+    #[test]
+    fn screen_helpers_follow_the_camera() {
+        let mut window = scrolled_window();
+        let visible = window.add_sprite(block((250.0, 50.0), SpriteType::Enemy));
+        let behind = window.add_sprite(block((50.0, 50.0), SpriteType::Enemy));
+
+        window.change_health_offscreen(SpriteType::Enemy, -5);
+        assert_eq!(window.sprite(visible).unwrap().health, 10);
+        assert_eq!(window.sprite(behind).unwrap().health, 5);
+
+        window.remove_if_out_of_screen(SpriteType::Enemy);
+        assert!(window.sprite(visible).is_some());
+        assert!(window.sprite(behind).is_none());
+    }
+
+    #[test]
+    fn prevent_leaving_screen_clamps_to_the_camera_view() {
+        let mut window = scrolled_window();
+        window.camera.zoom = 2;
+        let player = window.add_sprite(block((260.0, -5.0), SpriteType::Player));
+        let mut hud = block((-5.0, 95.0), SpriteType::Player);
+        hud.layer = Layer::HUD;
+        let hud = window.add_sprite(hud);
+
+        window.prevent_leaving_screen(SpriteType::Player);
+        assert_eq!(window.sprite(player).unwrap().position, (240.0, 0.0));
+        assert_eq!(window.sprite(hud).unwrap().position, (0.0, 90.0));
+    }
+}
@@ -0,0 +1,273 @@
+use std::ops::{Index, IndexMut};
+use super::Sprite;
+
+/// Stable handle to a sprite stored in a [`SpriteStore`].
+///
+/// Handles stay valid while the sprite exists, no matter how many other
+/// sprites are added or removed. Once the sprite is removed, its handle
+/// becomes stale: lookups return `None` instead of silently resolving to a
+/// different sprite, even if the storage slot is later reused.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SpriteId {
+    index: u32,
+    generation: u32,
+}
+
+/// Bookkeeping for one handle slot.
+struct Slot {
+    /// Incremented every time the slot is freed.
+    generation: u32,
+
+    /// Position of the sprite in the dense list, if the slot is occupied.
+    dense: Option<usize>,
+}
+
+/// Sprite storage addressed by generational [`SpriteId`] handles.
+///
+/// Sprites are kept densely in insertion order, so iteration (and therefore
+/// drawing) order matches the order in which sprites were added. Removing a
+/// sprite preserves the order of the remaining ones.
+pub struct SpriteStore {
+    sprites: Vec<Sprite>,
+    ids: Vec<SpriteId>,
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+}
+
+impl Default for SpriteStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpriteStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self {
+            sprites: Vec::new(),
+            ids: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Adds a sprite and returns its handle.
+    pub fn insert(&mut self, sprite: Sprite) -> SpriteId {
+        let dense = self.sprites.len();
+
+        let id = if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.dense = Some(dense);
+            SpriteId { index, generation: slot.generation }
+        } else {
+            self.slots.push(Slot { generation: 0, dense: Some(dense) });
+            SpriteId { index: (self.slots.len() - 1) as u32, generation: 0 }
+        };
+
+        self.sprites.push(sprite);
+        self.ids.push(id);
+        id
+    }
+
+    /// Returns the dense position of a live handle.
+    fn dense(&self, id: SpriteId) -> Option<usize> {
+        let slot = self.slots.get(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.dense
+    }
+
+    /// Returns `true` if the handle refers to a live sprite.
+    pub fn contains(&self, id: SpriteId) -> bool {
+        self.dense(id).is_some()
+    }
+
+    /// Returns the sprite for a handle, or `None` if the handle is stale.
+    pub fn get(&self, id: SpriteId) -> Option<&Sprite> {
+        self.dense(id).map(|i| &self.sprites[i])
+    }
+
+    /// Returns the sprite for a handle mutably, or `None` if the handle is stale.
+    pub fn get_mut(&mut self, id: SpriteId) -> Option<&mut Sprite> {
+        self.dense(id).map(|i| &mut self.sprites[i])
+    }
+
+    /// Removes a sprite and returns it, or `None` if the handle is stale.
+    ///
+    /// The handle and every copy of it become stale.
+    pub fn remove(&mut self, id: SpriteId) -> Option<Sprite> {
+        let dense = self.dense(id)?;
+
+        let slot = &mut self.slots[id.index as usize];
+        slot.dense = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+
+        self.ids.remove(dense);
+        for moved in &self.ids[dense..] {
+            if let Some(d) = self.slots[moved.index as usize].dense.as_mut() {
+                *d -= 1;
+            }
+        }
+
+        Some(self.sprites.remove(dense))
+    }
+
+    /// Keeps only the sprites for which `keep` returns `true`.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(SpriteId, &Sprite) -> bool,
+    {
+        let removed: Vec<SpriteId> = self
+            .iter()
+            .filter(|&(id, sprite)| !keep(id, sprite))
+            .map(|(id, _)| id)
+            .collect();
+
+        for id in removed {
+            self.remove(id);
+        }
+    }
+
+    /// Removes all sprites. Every existing handle becomes stale.
+    pub fn clear(&mut self) {
+        for id in std::mem::take(&mut self.ids) {
+            let slot = &mut self.slots[id.index as usize];
+            slot.dense = None;
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free.push(id.index);
+        }
+        self.sprites.clear();
+    }
+
+    /// Returns the number of live sprites.
+    pub fn len(&self) -> usize {
+        self.sprites.len()
+    }
+
+    /// Returns `true` if the store holds no sprites.
+    pub fn is_empty(&self) -> bool {
+        self.sprites.is_empty()
+    }
+
+    /// Returns the handles of all live sprites in insertion order.
+    pub fn ids(&self) -> &[SpriteId] {
+        &self.ids
+    }
+
+    /// Iterates over `(handle, sprite)` pairs in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (SpriteId, &Sprite)> {
+        self.ids.iter().copied().zip(self.sprites.iter())
+    }
+
+    /// Iterates mutably over `(handle, sprite)` pairs in insertion order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (SpriteId, &mut Sprite)> {
+        self.ids.iter().copied().zip(self.sprites.iter_mut())
+    }
+
+    /// Returns all sprites in insertion order.
+    pub fn values(&self) -> &[Sprite] {
+        &self.sprites
+    }
+
+    /// Returns all sprites mutably in insertion order.
+    pub fn values_mut(&mut self) -> &mut [Sprite] {
+        &mut self.sprites
+    }
+}
+
+impl Index<SpriteId> for SpriteStore {
+    type Output = Sprite;
+
+    /// # Panics
+    /// Panics if the handle is stale. Use [`SpriteStore::get`] to handle
+    /// removed sprites gracefully.
+    fn index(&self, id: SpriteId) -> &Sprite {
+        self.get(id).expect("stale SpriteId: sprite was removed")
+    }
+}
+
+impl IndexMut<SpriteId> for SpriteStore {
+    fn index_mut(&mut self, id: SpriteId) -> &mut Sprite {
+        self.get_mut(id).expect("stale SpriteId: sprite was removed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprites::SpriteType;
+
+    fn sprite(health: i32) -> Sprite {
+        Sprite::new_color((0.0, 0.0), (1, 1), SpriteType::Wall, health, 0xFFFFFF, false)
+    }
+
+    fn healths(store: &SpriteStore) -> Vec<i32> {
+        store.values().iter().map(|sprite| sprite.health).collect()
+    }
+
+    #[test]
+    fn removed_ids_are_stale() {
+        let mut store = SpriteStore::new();
+        let id = store.insert(sprite(1));
+        assert_eq!(store.remove(id).map(|sprite| sprite.health), Some(1));
+
+        assert!(!store.contains(id));
+        assert!(store.get(id).is_none());
+        assert!(store.get_mut(id).is_none());
+        assert!(store.remove(id).is_none());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn reused_slots_get_a_new_generation() {
+        let mut store = SpriteStore::new();
+        let old = store.insert(sprite(1));
+        store.remove(old);
+        let new = store.insert(sprite(2));
+
+        assert_eq!(new.index, old.index);
+        assert_ne!(new.generation, old.generation);
+        assert!(store.get(old).is_none());
+        assert!(store.remove(old).is_none());
+        assert_eq!(store[new].health, 2);
+    }
+
+    #[test]
+    fn removal_keeps_iteration_order() {
+        let mut store = SpriteStore::new();
+        let ids: Vec<SpriteId> = (1..=4).map(|health| store.insert(sprite(health))).collect();
+        store.remove(ids[1]);
+
+        assert_eq!(healths(&store), [1, 3, 4]);
+        assert_eq!(store.ids(), [ids[0], ids[2], ids[3]]);
+        for (id, sprite) in store.iter() {
+            assert_eq!(store[id].health, sprite.health);
+        }
+
+        let added = store.insert(sprite(5));
+        assert_eq!(healths(&store), [1, 3, 4, 5]);
+        assert_eq!(store.ids().last(), Some(&added));
+    }
+
+    #[test]
+    fn retain_and_clear_invalidate_ids() {
+        let mut store = SpriteStore::new();
+        let ids: Vec<SpriteId> = (1..=4).map(|health| store.insert(sprite(health))).collect();
+
+        store.retain(|_, sprite| sprite.health % 2 == 0);
+        assert_eq!(healths(&store), [2, 4]);
+        assert!(store.get(ids[0]).is_none());
+        assert!(store.get(ids[2]).is_none());
+        assert_eq!(store[ids[3]].health, 4);
+
+        store.clear();
+        assert!(store.is_empty());
+        assert!(ids.iter().all(|&id| !store.contains(id)));
+
+        let reused = store.insert(sprite(6));
+        assert!(ids.iter().all(|&id| store.get(id).is_none()));
+        assert_eq!(store[reused].health, 6);
+    }
+}
@@ -1,5 +1,4 @@
 use super::{SpriteType, Vector, Sprite};
-use std::collections::HashSet;
 use crate::Window;
 
 
@@ -9,8 +8,8 @@ impl Sprite {
     /// If a velocity vector already exists, it is replaced.
     /// Otherwise, a new velocity vector is added.
     ///
-    /// Values represent `(dx, dy)` in pixels per update tick.
-    pub fn set_velocity(&mut self, vx: i32, vy: i32) {
+    /// Values represent `(dx, dy)` in pixels per update tick and may be fractional.
+    pub fn set_velocity(&mut self, vx: f32, vy: f32) {
         if let Some(Vector::Velocity(x, y)) =
             self.vectors.iter_mut().find(|v| matches!(v, Vector::Velocity(_, _)))
         {
@@ -25,7 +24,7 @@ impl Sprite {
     ///
     /// - `None` leaves the corresponding component unchanged.
     /// - If no velocity vector exists, one is created.
-    pub fn update_velocity(&mut self, vx: Option<i32>, vy: Option<i32>) {
+    pub fn update_velocity(&mut self, vx: Option<f32>, vy: Option<f32>) {
         if let Some(Vector::Velocity(x, y)) =
             self.vectors.iter_mut().find(|v| matches!(v, Vector::Velocity(_, _)))
         {
@@ -33,8 +32,8 @@ impl Sprite {
             if let Some(vy) = vy { *y = vy; }
         } else {
             self.vectors.push(Vector::Velocity(
-                vx.unwrap_or(0),
-                vy.unwrap_or(0),
+                vx.unwrap_or(0.0),
+                vy.unwrap_or(0.0),
             ));
         }
     }
@@ -51,7 +50,7 @@ impl Sprite {
     ///
     /// Returns `Some((dx, dy))` if a velocity vector exists,
     /// or `None` if the sprite has no velocity.
-    pub fn velocity(&self) -> Option<(i32, i32)> {
+    pub fn velocity(&self) -> Option<(f32, f32)> {
         self.vectors.iter().find_map(|v| {
             if let Vector::Velocity(x, y) = *v {
                 Some((x, y))
@@ -67,7 +66,7 @@ impl Sprite {
     /// Otherwise, a new acceleration vector is added.
     ///
     /// Values represent `(ax, ay)` in pixels per tick².
-    pub fn set_acceleration(&mut self, ax: i32, ay: i32) {
+    pub fn set_acceleration(&mut self, ax: f32, ay: f32) {
         if let Some(Vector::Acceleration(x, y)) =
             self.vectors.iter_mut().find(|v| matches!(v, Vector::Acceleration(_, _)))
         {
@@ -92,7 +91,7 @@ impl Sprite {
 impl Window {
     /// Add one or multiple vectors to all sprites of a given type
     pub fn add_vector(&mut self, sprite_type: SpriteType, vector: Vector) {
-        for sprite in self.sprites.iter_mut() {
+        for sprite in self.sprites.values_mut() {
             if sprite.sprite_type != sprite_type {
                 continue;
             }
@@ -128,7 +127,7 @@ impl Window {
     /// Add multiple vectors at once, preventing duplicates
     pub fn add_vectors(&mut self, sprite_type: SpriteType, vectors: impl Into<Vec<Vector>>) {
         let vecs: Vec<Vector> = vectors.into();
-        for sprite in self.sprites.iter_mut() {
+        for sprite in self.sprites.values_mut() {
             if sprite.sprite_type == sprite_type {
                 for &v in &vecs {
                     if !sprite.vectors.contains(&v) {
@@ -141,7 +140,7 @@ impl Window {
 
     /// Remove specific vectors or all vectors of a type
     pub fn remove_vectors(&mut self, sprite_type: SpriteType, vector_to_remove: Option<Vector>) {
-        for sprite in self.sprites.iter_mut() {
+        for sprite in self.sprites.values_mut() {
             if sprite.sprite_type != sprite_type { continue; }
 
             if let Some(v) = vector_to_remove {
@@ -155,16 +154,16 @@ impl Window {
     /// Update sprite positions based on velocity and acceleration vectors
     /// Apply vectors to update sprite positions
     pub fn apply_vectors(&mut self) {
-        for sprite in self.sprites.iter_mut() {
-            let mut dx = 0;
-            let mut dy = 0;
-            let mut seen = HashSet::new();
+        for sprite in self.sprites.values_mut() {
+            let mut dx = 0.0;
+            let mut dy = 0.0;
+            let mut seen: Vec<Vector> = Vec::new();
 
             // --- First pass: apply acceleration to velocity ---
             let mut accel_list = Vec::new();
             for vec in &sprite.vectors {
                 if seen.contains(vec) { continue; }
-                seen.insert(*vec);
+                seen.push(*vec);
 
                 if let Vector::Acceleration(ax, ay) = vec {
                     accel_list.push((*ax, *ay));
@@ -192,7 +191,7 @@ impl Window {
             }
 
             let (x, y) = sprite.position;
-            sprite.position = ((x as i32 + dx) as usize, (y as i32 + dy) as usize);
+            sprite.position = (x + dx, y + dy);
         }
     }
 
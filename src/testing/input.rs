@@ -0,0 +1,275 @@
+use std::time::Duration;
+use crate::Window;
+use crate::controls::{GamepadAxis, GamepadButton, InputEvent, InputRecording, Key, RecordedFrame};
+
+/// Scripted input, laid out frame by frame.
+///
+/// Methods add events to the current frame; [`Self::next_frame`] and
+/// [`Self::wait`] move on. A script is played back on a window with
+/// [`play_script`] (or [`Window::play_recording`] after converting it into
+/// an [`InputRecording`]), replacing live devices and giving every frame
+/// the same fixed duration, so menu flow and gameplay can be tested end
+/// to end on a [`Window::headless`] window.
+///
+/// # Example
+/// ```
+/// use carose::{Menu, Window};
+/// use carose::controls::Key;
+/// use carose::testing::{play_script, InputScript};
+///
+/// let mut window = Window::headless(320, 240);
+/// let mut menu = Menu::new(vec!["Start", "Options", "Quit"], 0xFFFFFF, 0xFFFF00);
+/// let mut chosen = None;
+///
+/// let script = InputScript::new()
+///     .tap(Key::Down)
+///     .next_frame()
+///     .tap(Key::Enter);
+///
+/// play_script(&mut window, script, |window, _| {
+///     if let Some(item) = menu.update(window) {
+///         chosen = Some(item);
+///     }
+/// });
+/// assert_eq!(chosen, Some("Options"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputScript {
+    frames: Vec<Vec<InputEvent>>,
+    frame_time: Duration,
+}
+
+impl Default for InputScript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputScript {
+    /// Creates a script of one empty frame, at 60 frames per second.
+    pub fn new() -> Self {
+        Self {
+            frames: vec![Vec::new()],
+            frame_time: Duration::from_secs(1) / 60,
+        }
+    }
+
+    /// Sets the duration of every frame, which drives delta time, held
+    /// durations and key repeat during playback.
+    pub fn with_frame_time(mut self, frame_time: Duration) -> Self {
+        self.frame_time = frame_time;
+        self
+    }
+
+    /// Adds an event to the current frame.
+    pub fn event(mut self, event: InputEvent) -> Self {
+        self.frames.last_mut().expect("a script always has a frame").push(event);
+        self
+    }
+
+    /// Presses a key or mouse button on the current frame.
+    pub fn press(self, key: Key) -> Self {
+        self.event(InputEvent::KeyDown(key))
+    }
+
+    /// Releases a key or mouse button on the current frame.
+    pub fn release(self, key: Key) -> Self {
+        self.event(InputEvent::KeyUp(key))
+    }
+
+    /// Presses a key on the current frame and releases it on the next,
+    /// which becomes the current frame.
+    pub fn tap(self, key: Key) -> Self {
+        self.press(key).next_frame().release(key)
+    }
+
+    /// Presses a key on the current frame and releases it `frames` frames
+    /// later, on what becomes the current frame.
+    pub fn hold(self, key: Key, frames: usize) -> Self {
+        self.press(key).wait(frames).release(key)
+    }
+
+    /// Moves the cursor to a position relative to the presentation target,
+    /// which is the frame itself on a headless window.
+    pub fn move_mouse(self, x: i32, y: i32) -> Self {
+        self.event(InputEvent::CursorMoved(x, y))
+    }
+
+    /// Scrolls the mouse wheel; positive values scroll up.
+    pub fn scroll(self, delta: i32) -> Self {
+        self.event(InputEvent::Scroll(delta))
+    }
+
+    /// Types text on the current frame, as seen by
+    /// [`crate::controls::Input::typed_chars`].
+    ///
+    /// Only the character stream is affected; no key states change.
+    pub fn type_text(self, text: &str) -> Self {
+        text.chars().fold(self, |script, c| script.event(InputEvent::Char(c)))
+    }
+
+    /// Presses a gamepad button on the current frame.
+    pub fn press_button(self, slot: usize, button: GamepadButton) -> Self {
+        self.event(InputEvent::PadButtonDown(slot, button))
+    }
+
+    /// Releases a gamepad button on the current frame.
+    pub fn release_button(self, slot: usize, button: GamepadButton) -> Self {
+        self.event(InputEvent::PadButtonUp(slot, button))
+    }
+
+    /// Sets the raw value of a gamepad axis on the current frame.
+    pub fn set_axis(self, slot: usize, axis: GamepadAxis, value: f32) -> Self {
+        self.event(InputEvent::PadAxis(slot, axis, value))
+    }
+
+    /// Starts a new frame.
+    pub fn next_frame(self) -> Self {
+        self.wait(1)
+    }
+
+    /// Adds `frames` frames without input; the last becomes the current frame.
+    pub fn wait(mut self, frames: usize) -> Self {
+        self.frames.resize_with(self.frames.len() + frames, Vec::new);
+        self
+    }
+
+    /// Returns the number of frames in the script.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frame of the script has any events.
+    ///
+    /// A script always has at least one frame, so this does not mean
+    /// [`Self::len`] is zero.
+    pub fn is_empty(&self) -> bool {
+        self.frames.iter().all(Vec::is_empty)
+    }
+}
+
+impl From<InputScript> for InputRecording {
+    fn from(script: InputScript) -> Self {
+        let mut recording = InputRecording::new();
+        for events in script.frames {
+            recording.push(RecordedFrame {
+                delta: script.frame_time,
+                focused: true,
+                events,
+            });
+        }
+        recording
+    }
+}
+
+/// Plays a script on a window, running one frame per scripted frame.
+///
+/// Like [`super::render_frames`], each frame polls input, calls `frame`
+/// with the window and the frame number, then draws. Every key and button
+/// is released before the first frame, and live input resumes afterwards.
+///
+/// # Parameters
+/// - `window`: The window to drive.
+/// - `script`: Input for each frame.
+/// - `frame`: Per-frame game logic.
+pub fn play_script<F>(window: &mut Window, script: InputScript, mut frame: F)
+where
+    F: FnMut(&mut Window, usize),
+{
+    window.play_recording(script.into());
+    let mut index = 0;
+    while window.is_replaying() {
+        window.update_controls();
+        frame(window, index);
+        window.draw();
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays `script` on a headless window and returns `probe` of the
+    /// window's input on every frame.
+    fn play<T>(script: InputScript, mut probe: impl FnMut(&Window) -> T) -> Vec<T> {
+        let mut window = Window::headless(8, 8);
+        let mut seen = Vec::new();
+        play_script(&mut window, script, |window, _| seen.push(probe(window)));
+        seen
+    }
+
+    #[test]
+    fn tap_presses_then_releases_on_the_next_frame() {
+        let edges = play(InputScript::new().tap(Key::Space).next_frame(), |window| {
+            (window.controls.just_pressed(Key::Space), window.controls.just_released(Key::Space))
+        });
+        assert_eq!(edges, [(true, false), (false, true), (false, false)]);
+    }
+
+    #[test]
+    fn hold_keeps_the_key_down_for_that_many_frames() {
+        let held = play(InputScript::new().hold(Key::Space, 3).next_frame(), |window| {
+            window.controls.pressed(Key::Space)
+        });
+        assert_eq!(held, [true, true, true, false, false]);
+    }
+
+    #[test]
+    fn typed_text_reaches_typed_chars() {
+        let typed = play(InputScript::new().next_frame().type_text("hé!").next_frame(), |window| {
+            window.controls.typed_chars().iter().collect::<String>()
+        });
+        assert_eq!(typed, ["", "hé!", ""]);
+    }
+
+    #[test]
+    fn frame_time_drives_delta_time() {
+        let script = InputScript::new().wait(2).with_frame_time(Duration::from_millis(250));
+        assert_eq!(play(script, |window| window.clock.delta()), [Duration::from_millis(250); 3]);
+    }
+
+    #[test]
+    fn injected_events_survive_update_controls() {
+        let mut window = Window::headless(8, 8);
+        window.inject(InputEvent::KeyDown(Key::Space));
+        window.inject(InputEvent::Char('x'));
+        window.update_controls();
+        assert!(window.controls.just_pressed(Key::Space));
+        assert_eq!(window.controls.typed_chars(), ['x']);
+
+        window.update_controls();
+        assert!(window.controls.pressed(Key::Space));
+        assert!(!window.controls.just_pressed(Key::Space));
+        assert!(window.controls.typed_chars().is_empty());
+    }
+
+    #[test]
+    fn live_input_resumes_after_the_script() {
+        let mut window = Window::headless(8, 8);
+        window.inject(InputEvent::KeyDown(Key::Enter));
+        play_script(&mut window, InputScript::new().press(Key::Space).next_frame(), |window, frame| {
+            assert!(!window.controls.pressed(Key::Enter));
+            // Ignored, since the next frame is still played back.
+            if frame == 0 {
+                window.inject(InputEvent::KeyDown(Key::Tab));
+            }
+        });
+        assert!(!window.is_replaying());
+        assert!(window.controls.pressed(Key::Space));
+
+        window.inject(InputEvent::KeyUp(Key::Space));
+        window.update_controls();
+        assert!(window.controls.just_released(Key::Space));
+        assert!(!window.controls.pressed(Key::Tab));
+        assert_eq!(window.clock.delta(), Duration::from_secs(1) / 60);
+    }
+
+    #[test]
+    fn is_empty_means_no_events() {
+        assert!(InputScript::new().is_empty());
+        assert!(InputScript::new().wait(3).is_empty());
+        assert_eq!(InputScript::new().wait(3).len(), 4);
+        assert!(!InputScript::new().wait(3).tap(Key::Space).is_empty());
+    }
+}
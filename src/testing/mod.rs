@@ -0,0 +1,360 @@
+mod input;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::Window;
+use crate::image::save_image;
+
+pub use input::{play_script, InputScript};
+
+/// Environment variable that turns snapshot comparisons into snapshot updates.
+///
+/// When set to any value, [`compare_snapshot`] overwrites the reference image
+/// with the current frame instead of comparing against it.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "CAROSE_UPDATE_SNAPSHOTS";
+
+/// Reasons a frame did not match its reference image.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The reference image does not exist yet.
+    MissingReference(PathBuf),
+
+    /// The frame and the reference image have different dimensions.
+    SizeMismatch {
+        /// Size of the reference image.
+        expected: (usize, usize),
+
+        /// Size of the rendered frame.
+        actual: (usize, usize),
+    },
+
+    /// Some pixels differ by more than the allowed tolerance.
+    PixelMismatch {
+        /// Number of pixels outside the tolerance.
+        pixels: usize,
+
+        /// Largest per-channel difference found.
+        max_delta: u8,
+
+        /// Path of the written diff image.
+        diff: PathBuf,
+    },
+
+    /// An image could not be read or written.
+    Image(image::ImageError),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::MissingReference(path) => write!(
+                f,
+                "reference image {} does not exist (set {} to create it)",
+                path.display(),
+                UPDATE_SNAPSHOTS_ENV,
+            ),
+            SnapshotError::SizeMismatch { expected, actual } => write!(
+                f,
+                "frame is {}x{} but reference is {}x{}",
+                actual.0, actual.1, expected.0, expected.1,
+            ),
+            SnapshotError::PixelMismatch { pixels, max_delta, diff } => write!(
+                f,
+                "{} pixels differ (max channel delta {}), diff written to {}",
+                pixels,
+                max_delta,
+                diff.display(),
+            ),
+            SnapshotError::Image(err) => write!(f, "image error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<image::ImageError> for SnapshotError {
+    fn from(err: image::ImageError) -> Self {
+        SnapshotError::Image(err)
+    }
+}
+
+/// Runs a scripted number of frames on a window.
+///
+/// Each frame polls input, calls `script` with the window and the frame
+/// number (starting at `0`), then draws. This mirrors a normal game loop,
+/// so it is typically used with [`Window::headless`].
+///
+/// # Parameters
+/// - `window`: The window to drive.
+/// - `frames`: Number of frames to render.
+/// - `script`: Per-frame game logic.
+pub fn render_frames<F>(window: &mut Window, frames: usize, mut script: F)
+where
+    F: FnMut(&mut Window, usize),
+{
+    for frame in 0..frames {
+        window.update_controls();
+        script(window, frame);
+        window.draw();
+    }
+}
+
+/// Compares the window's last composed frame against a reference image.
+///
+/// A pixel matches when each of its red, green and blue channels is within
+/// `tolerance` of the reference. On mismatch, two images are written next
+/// to the reference:
+/// - `<name>.actual.png`: the rendered frame
+/// - `<name>.diff.png`: mismatched pixels in red over a dimmed copy of the frame
+///
+/// If [`UPDATE_SNAPSHOTS_ENV`] is set, the reference is (re)written from the
+/// current frame and the comparison always succeeds.
+///
+/// # Errors
+/// Returns a [`SnapshotError`] describing why the frame does not match.
+pub fn compare_snapshot<P: AsRef<Path>>(
+    window: &Window,
+    reference: P,
+    tolerance: u8,
+) -> Result<(), SnapshotError> {
+    let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some();
+    check_snapshot(window, reference.as_ref(), tolerance, update)
+}
+
+/// Compares against `reference`, or rewrites it when `update` is set.
+fn check_snapshot(window: &Window, reference: &Path, tolerance: u8, update: bool) -> Result<(), SnapshotError> {
+    let (width, height) = window.frame_size();
+    let frame = window.frame();
+
+    if update {
+        if let Some(parent) = reference.parent() {
+            std::fs::create_dir_all(parent).map_err(image::ImageError::IoError)?;
+        }
+        save_image(reference, frame, width, height)?;
+        return Ok(());
+    }
+
+    if !reference.exists() {
+        return Err(SnapshotError::MissingReference(reference.to_path_buf()));
+    }
+
+    let expected = image::open(reference)?.to_rgb8();
+    let expected_size = (expected.width() as usize, expected.height() as usize);
+    if expected_size != (width, height) {
+        return Err(SnapshotError::SizeMismatch {
+            expected: expected_size,
+            actual: (width, height),
+        });
+    }
+
+    let mut diff = vec![0u32; width * height];
+    let mut pixels = 0;
+    let mut max_delta = 0;
+
+    for (i, (&actual, expected)) in frame.iter().zip(expected.pixels()).enumerate() {
+        let [r, g, b] = expected.0;
+        let delta = channel_delta(actual, r, g, b);
+        max_delta = max_delta.max(delta);
+
+        if delta > tolerance {
+            pixels += 1;
+            diff[i] = 0xFF0000;
+        } else {
+            diff[i] = dim(actual);
+        }
+    }
+
+    if pixels == 0 {
+        return Ok(());
+    }
+
+    let diff_path = sibling(reference, "diff");
+    save_image(sibling(reference, "actual"), frame, width, height)?;
+    save_image(&diff_path, &diff, width, height)?;
+
+    Err(SnapshotError::PixelMismatch {
+        pixels,
+        max_delta,
+        diff: diff_path,
+    })
+}
+
+/// Asserts that the window's last composed frame matches a reference image.
+///
+/// This is a panicking wrapper around [`compare_snapshot`] meant for tests.
+///
+/// # Panics
+/// Panics with the [`SnapshotError`] message if the frame does not match.
+///
+/// # Example
+///
+/// ```no_run
+/// use carose::Window;
+/// use carose::testing::{render_frames, assert_snapshot};
+///
+/// let mut window = Window::headless(320, 240);
+/// window.set_background_color(0x202020);
+/// render_frames(&mut window, 3, |_, _| {});
+/// assert_snapshot(&window, "tests/snapshots/empty.png", 0);
+/// ```
+pub fn assert_snapshot<P: AsRef<Path>>(window: &Window, reference: P, tolerance: u8) {
+    if let Err(err) = compare_snapshot(window, reference.as_ref(), tolerance) {
+        panic!("snapshot {} failed: {}", reference.as_ref().display(), err);
+    }
+}
+
+/// Largest absolute channel difference between a `0xRRGGBB` pixel and an RGB triple.
+fn channel_delta(actual: u32, r: u8, g: u8, b: u8) -> u8 {
+    let ar = ((actual >> 16) & 0xFF) as u8;
+    let ag = ((actual >> 8) & 0xFF) as u8;
+    let ab = (actual & 0xFF) as u8;
+
+    ar.abs_diff(r).max(ag.abs_diff(g)).max(ab.abs_diff(b))
+}
+
+/// Converts a pixel to a dark gray of matching luminance for diff images.
+fn dim(color: u32) -> u32 {
+    let r = (color >> 16) & 0xFF;
+    let g = (color >> 8) & 0xFF;
+    let b = color & 0xFF;
+    let gray = (r * 299 + g * 587 + b * 114) / 1000 / 3;
+
+    (gray << 16) | (gray << 8) | gray
+}
+
+/// Builds `<stem>.<suffix>.png` next to the reference image.
+fn sibling(reference: &Path, suffix: &str) -> PathBuf {
+    let stem = reference
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    reference.with_file_name(format!("{}.{}.png", stem, suffix))
+}
+
+/// Returns a fresh, empty directory for the files one test writes.
+///
+/// The directory lives under the system temp directory and is keyed by
+/// `name` and the process id, so tests running in parallel never share one.
+#[cfg(test)]
+pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("carose-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws a 4x4 frame filled with `background` except for `dot` at (1, 1).
+    fn window_with(background: u32, dot: u32) -> Window {
+        let mut image = vec![vec![background; 4]; 4];
+        image[1][1] = dot;
+
+        let mut window = Window::headless(4, 4);
+        window.background = Some(crate::windows::Background::Image(image));
+        window.draw();
+        window
+    }
+
+    #[test]
+    fn identical_frames_match() {
+        let dir = scratch_dir("snapshot-match");
+        let reference = dir.join("frame.png");
+        let window = window_with(0x404040, 0xFFFFFF);
+        window.save_frame(&reference).unwrap();
+
+        assert!(compare_snapshot(&window, &reference, 0).is_ok());
+        assert!(!dir.join("frame.actual.png").exists());
+        assert!(!dir.join("frame.diff.png").exists());
+    }
+
+    #[test]
+    fn differences_within_tolerance_match() {
+        let dir = scratch_dir("snapshot-tolerance");
+        let reference = dir.join("frame.png");
+        window_with(0x404040, 0xFFFFFF).save_frame(&reference).unwrap();
+
+        let window = window_with(0x434040, 0xFFFFFF);
+        assert!(compare_snapshot(&window, &reference, 3).is_ok());
+        match compare_snapshot(&window, &reference, 2) {
+            Err(SnapshotError::PixelMismatch { pixels, max_delta, .. }) => {
+                assert_eq!(pixels, 15);
+                assert_eq!(max_delta, 3);
+            }
+            other => panic!("expected a pixel mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn mismatch_writes_actual_and_diff_images() {
+        let dir = scratch_dir("snapshot-diff");
+        let reference = dir.join("frame.png");
+        window_with(0x404040, 0xFFFFFF).save_frame(&reference).unwrap();
+
+        let window = window_with(0x404040, 0x000000);
+        let err = compare_snapshot(&window, &reference, 0).unwrap_err();
+        match &err {
+            SnapshotError::PixelMismatch { pixels, max_delta, diff } => {
+                assert_eq!(*pixels, 1);
+                assert_eq!(*max_delta, 0xFF);
+                assert_eq!(diff, &dir.join("frame.diff.png"));
+            }
+            other => panic!("expected a pixel mismatch, got {:?}", other),
+        }
+
+        let actual = image::open(dir.join("frame.actual.png")).unwrap().to_rgb8();
+        assert_eq!(actual.get_pixel(1, 1).0, [0, 0, 0]);
+        assert_eq!(actual.get_pixel(0, 0).0, [0x40, 0x40, 0x40]);
+
+        let diff = image::open(dir.join("frame.diff.png")).unwrap().to_rgb8();
+        assert_eq!(diff.get_pixel(1, 1).0, [0xFF, 0, 0]);
+        assert_eq!(diff.get_pixel(0, 0).0, [0x15, 0x15, 0x15]);
+    }
+
+    #[test]
+    fn size_mismatch_is_reported() {
+        let dir = scratch_dir("snapshot-size");
+        let reference = dir.join("frame.png");
+        let mut small = Window::headless(2, 3);
+        small.draw();
+        small.save_frame(&reference).unwrap();
+
+        let window = window_with(0x404040, 0xFFFFFF);
+        match compare_snapshot(&window, &reference, 0) {
+            Err(SnapshotError::SizeMismatch { expected, actual }) => {
+                assert_eq!(expected, (2, 3));
+                assert_eq!(actual, (4, 4));
+            }
+            other => panic!("expected a size mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn missing_reference_is_reported() {
+        let dir = scratch_dir("snapshot-missing");
+        let window = window_with(0x404040, 0xFFFFFF);
+        assert!(matches!(
+            compare_snapshot(&window, dir.join("none.png"), 0),
+            Err(SnapshotError::MissingReference(_)),
+        ));
+    }
+
+    #[test]
+    fn update_mode_rewrites_the_reference() {
+        let dir = scratch_dir("snapshot-update");
+        let reference = dir.join("nested").join("frame.png");
+
+        let window = window_with(0x102030, 0x000000);
+        assert!(check_snapshot(&window, &reference, 0, true).is_ok());
+        assert!(compare_snapshot(&window, &reference, 0).is_ok());
+
+        // An existing reference is overwritten rather than compared.
+        let other = window_with(0x404040, 0xFFFFFF);
+        assert!(check_snapshot(&other, &reference, 0, true).is_ok());
+        assert!(compare_snapshot(&other, &reference, 0).is_ok());
+        assert!(!dir.join("nested").join("frame.diff.png").exists());
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{Window, TextAlign};
+use crate::controls::Key;
+use crate::windows::text::measure_text;
+
+/// Seconds the cursor stays on or off while blinking.
+const BLINK_INTERVAL: f32 = 0.5;
+
+/// Characters a [`TextInput`] accepts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Charset {
+    /// Printable ASCII, i.e. everything the built-in font can draw.
+    Printable,
+
+    /// ASCII letters, digits and space.
+    Alphanumeric,
+
+    /// ASCII digits.
+    Numeric,
+
+    /// Exactly the characters in the string.
+    Custom(String),
+}
+
+impl Charset {
+    /// Returns `true` if `c` may be typed.
+    pub fn allows(&self, c: char) -> bool {
+        match self {
+            Charset::Printable => (' '..='~').contains(&c),
+            Charset::Alphanumeric => c.is_ascii_alphanumeric() || c == ' ',
+            Charset::Numeric => c.is_ascii_digit(),
+            Charset::Custom(chars) => chars.contains(c),
+        }
+    }
+}
+
+/// A single-line text entry field.
+///
+/// The field reads typed characters from
+/// [`Input::typed_chars`](crate::controls::Input::typed_chars) and editing
+/// keys from [`Window::controls`]:
+/// - [`Key::Backspace`] deletes the character before the cursor.
+/// - [`Key::Left`] and [`Key::Right`] move the cursor.
+/// - [`Key::Enter`] submits the value.
+///
+/// Editing keys auto-repeat while held, following
+/// [`Input::key_repeat`](crate::controls::Input::key_repeat).
+///
+/// Like [`crate::Menu`], the field renders itself through text items whose
+/// ids start with a prefix chosen by the caller.
+///
+/// # Example
+/// ```no_run
+/// use carose::{Window, TextInput};
+///
+/// let mut window = Window::new("High Score", 640, 480);
+/// let mut name = TextInput::new((200, 220), 12);
+///
+/// while window.is_open() {
+///     window.update_controls();
+///     if let Some(name) = name.update(&mut window) {
+///         println!("Saved score for {}", name);
+///         break;
+///     }
+///     name.draw(&mut window, "name");
+///     window.draw();
+/// }
+/// ```
+pub struct TextInput {
+    /// Current text.
+    value: Vec<char>,
+
+    /// Cursor position as a character index into `value`.
+    cursor: usize,
+
+    /// Time since the cursor last moved, for blinking.
+    blink: f32,
+
+    /// Top-left corner of the field.
+    pub position: (usize, usize),
+
+    /// Font scaling factor.
+    pub size: usize,
+
+    /// Text color in 0xRRGGBB format.
+    pub color: u32,
+
+    /// Maximum number of characters.
+    pub max_length: usize,
+
+    /// Characters that may be typed.
+    pub charset: Charset,
+
+    /// Whether the field reacts to input and shows its cursor.
+    pub focused: bool,
+}
+
+impl TextInput {
+    /// Creates an empty, focused field accepting printable ASCII.
+    ///
+    /// # Parameters
+    /// - `position`: Top-left corner of the field.
+    /// - `max_length`: Maximum number of characters.
+    pub fn new(position: (usize, usize), max_length: usize) -> Self {
+        Self {
+            value: Vec::new(),
+            cursor: 0,
+            blink: 0.0,
+            position,
+            size: 3,
+            color: 0xFFFFFF,
+            max_length,
+            charset: Charset::Printable,
+            focused: true,
+        }
+    }
+
+    /// Returns the current text.
+    pub fn value(&self) -> String {
+        self.value.iter().collect()
+    }
+
+    /// Replaces the text and moves the cursor to its end.
+    ///
+    /// Characters outside the charset are dropped and the text is cut to
+    /// the maximum length.
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value
+            .chars()
+            .filter(|&c| self.charset.allows(c))
+            .take(self.max_length)
+            .collect();
+        self.cursor = self.value.len();
+    }
+
+    /// Returns the cursor position as a character index.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Processes this frame's input.
+    ///
+    /// Returns the text when [`Key::Enter`] is pressed, and `None`
+    /// otherwise. Unfocused fields ignore input. Call this once per frame
+    /// after [`Window::update_controls`].
+    pub fn update(&mut self, window: &mut Window) -> Option<String> {
+        self.blink += window.clock.delta_secs();
+        if !self.focused {
+            return None;
+        }
+
+        let controls = &window.controls;
+        let before = (self.value.len(), self.cursor);
+
+        for &c in controls.typed_chars() {
+            if self.value.len() < self.max_length && self.charset.allows(c) {
+                self.value.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+        }
+
+        if controls.repeated(Key::Backspace) && self.cursor > 0 {
+            self.cursor -= 1;
+            self.value.remove(self.cursor);
+        }
+        if controls.repeated(Key::Left) {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+        if controls.repeated(Key::Right) {
+            self.cursor = (self.cursor + 1).min(self.value.len());
+        }
+
+        if (self.value.len(), self.cursor) != before {
+            self.blink = 0.0;
+        }
+
+        controls.just_pressed(Key::Enter).then(|| self.value())
+    }
+
+    /// Draws the field to the given window.
+    ///
+    /// Any previously drawn text using the same `id_prefix` is removed
+    /// before drawing.
+    ///
+    /// # Parameters
+    /// - `window`: The window to render into.
+    /// - `id_prefix`: A unique identifier prefix used for text elements.
+    pub fn draw(&self, window: &mut Window, id_prefix: &str) {
+        let value_id = format!("{}_value", id_prefix);
+        let cursor_id = format!("{}_cursor", id_prefix);
+        window.remove_text(&cursor_id);
+
+        let (x, y) = self.position;
+        window.show_text(&value_id, &self.value(), (x, y), self.size, self.color, TextAlign::Left);
+
+        if self.focused && ((self.blink / BLINK_INTERVAL) as u32).is_multiple_of(2) {
+            // The cursor underlines the cell of the character at `cursor`,
+            // just below the glyphs so it never hides their bottom row.
+            let before: String = self.value[..self.cursor].iter().collect();
+            let cursor_x = x + measure_text(&format!("{}_", before), self.size).0
+                - measure_text("_", self.size).0;
+            let cursor_y = y + 2 * self.size;
+            window.show_text(&cursor_id, "_", (cursor_x, cursor_y), self.size, self.color, TextAlign::Left);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+    use crate::controls::KeyRepeat;
+    use crate::testing::{play_script, InputScript};
+
+    /// Plays `script` into `field` on a headless window and returns the
+    /// frames on which it submitted, with the submitted text.
+    ///
+    /// Frames last 250 ms and keys repeat after 500 ms, twice per second,
+    /// so a held key acts on its first frame and on every second frame
+    /// after that.
+    fn type_into(field: &mut TextInput, script: InputScript) -> Vec<(usize, String)> {
+        let mut window = Window::headless(200, 40);
+        window.controls.set_key_repeat(KeyRepeat { delay: Duration::from_millis(500), rate: 2.0 });
+        let mut submitted = Vec::new();
+        play_script(&mut window, script.with_frame_time(Duration::from_millis(250)), |window, frame| {
+            if let Some(value) = field.update(window) {
+                submitted.push((frame, value));
+            }
+        });
+        submitted
+    }
+
+    #[test]
+    fn charset_filters_typed_characters() {
+        let mut field = TextInput::new((0, 0), 10);
+        field.charset = Charset::Numeric;
+        type_into(&mut field, InputScript::new().type_text("a1b2 3!"));
+        assert_eq!(field.value(), "123");
+
+        field.charset = Charset::Custom("xy".to_string());
+        field.set_value("xaybz");
+        assert_eq!(field.value(), "xy");
+    }
+
+    #[test]
+    fn typing_stops_at_max_length() {
+        let mut field = TextInput::new((0, 0), 3);
+        type_into(&mut field, InputScript::new().type_text("ab").next_frame().type_text("cdef"));
+        assert_eq!(field.value(), "abc");
+        assert_eq!(field.cursor(), 3);
+
+        field.set_value("uvwxyz");
+        assert_eq!(field.value(), "uvw");
+    }
+
+    #[test]
+    fn backspace_and_arrows_edit_at_the_cursor() {
+        let mut field = TextInput::new((0, 0), 10);
+        let script = InputScript::new()
+            .type_text("abc")
+            .tap(Key::Left)
+            .tap(Key::Backspace)
+            .type_text("x")
+            .tap(Key::Right)
+            .tap(Key::Right);
+        type_into(&mut field, script);
+        assert_eq!(field.value(), "axc");
+        assert_eq!(field.cursor(), 3);
+    }
+
+    #[test]
+    fn held_editing_keys_repeat() {
+        let mut field = TextInput::new((0, 0), 10);
+        let script = InputScript::new()
+            .type_text("abcdef")
+            .next_frame()
+            .hold(Key::Backspace, 5)
+            .hold(Key::Left, 3);
+        type_into(&mut field, script);
+        assert_eq!(field.value(), "abc");
+        assert_eq!(field.cursor(), 1);
+    }
+
+    #[test]
+    fn enter_submits_the_value_once() {
+        let mut field = TextInput::new((0, 0), 10);
+        let script = InputScript::new().type_text("hi").next_frame().tap(Key::Enter).wait(2);
+        assert_eq!(type_into(&mut field, script), [(1, "hi".to_string())]);
+        assert_eq!(field.value(), "hi");
+
+        field.focused = false;
+        assert!(type_into(&mut field, InputScript::new().type_text("x").tap(Key::Enter)).is_empty());
+        assert_eq!(field.value(), "hi");
+    }
+}
@@ -0,0 +1,326 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use minifb::{InputCallback, Key as MfKey, MouseButton, MouseMode, Window as MfWindow, WindowOptions};
+use crate::controls::{diff_focused_keys, InputEvent, Key};
+
+/// A presentation target for frames composed by [`super::Window`].
+///
+/// The window renders every frame into a flat `0xRRGGBB` buffer and hands
+/// it to its backend, which decides what "showing" a frame means. This keeps
+/// the render path identical whether frames end up on screen, in memory, or
+/// anywhere else a custom backend sends them.
+///
+/// Methods that only make sense for OS windows (title, position, cursor)
+/// have no-op defaults so that simple backends only need to implement the
+/// core presentation and size queries.
+pub trait Backend {
+    /// Presents a fully composed frame.
+    ///
+    /// `buffer` holds `width * height` pixels in row-major order.
+    fn present(&mut self, buffer: &[u32], width: usize, height: usize);
+
+    /// Returns whether the presentation target is still open.
+    fn is_open(&self) -> bool;
+
+    /// Returns whether the target currently has input focus.
+    fn is_active(&mut self) -> bool;
+
+    /// Returns the current size of the target in pixels.
+    fn get_size(&self) -> (usize, usize);
+
+    /// Sets the target frames per second.
+    fn set_target_fps(&mut self, _fps: usize) {}
+
+    /// Updates the title of the target.
+    fn set_title(&mut self, _title: &str) {}
+
+    /// Moves the target to a new position in screen coordinates.
+    fn set_position(&mut self, _x: isize, _y: isize) {}
+
+    /// Returns the position of the target in screen coordinates.
+    fn get_position(&self) -> (isize, isize) {
+        (0, 0)
+    }
+
+    /// Keeps the target above all other windows when enabled.
+    fn topmost(&mut self, _topmost: bool) {}
+
+    /// Shows or hides the mouse cursor over the target.
+    fn set_cursor_visibility(&mut self, _visible: bool) {}
+
+    /// Appends the input events the target received since the last call.
+    ///
+    /// Key and mouse button events report transitions, cursor positions
+    /// are relative to the target, and typed characters are delivered in
+    /// order. Backends without input report nothing.
+    fn poll_events(&mut self, _events: &mut Vec<InputEvent>) {}
+}
+
+/// Backend that presents frames in a native OS window through `minifb`.
+///
+/// This is the backend used by [`super::Window::new`].
+pub struct MinifbBackend {
+    window: MfWindow,
+    chars: Rc<RefCell<Vec<char>>>,
+    held: Vec<Key>,
+    cursor: Option<(i32, i32)>,
+}
+
+/// Collects characters reported by `minifb` until the backend takes them.
+struct CharQueue(Rc<RefCell<Vec<char>>>);
+
+impl InputCallback for CharQueue {
+    fn add_char(&mut self, uni_char: u32) {
+        if let Some(c) = char::from_u32(uni_char) {
+            self.0.borrow_mut().push(c);
+        }
+    }
+}
+
+impl MinifbBackend {
+    /// Opens a resizable OS window with a default target of 60 FPS.
+    ///
+    /// # Panics
+    /// Panics if the window cannot be created.
+    pub fn new(title: &str, width: usize, height: usize) -> Self {
+        let mut window = MfWindow::new(
+            title,
+            width,
+            height,
+            WindowOptions {
+                resize: true,
+                ..WindowOptions::default()
+            },
+        )
+        .expect("Failed to create window");
+
+        window.set_target_fps(60);
+
+        let chars = Rc::new(RefCell::new(Vec::new()));
+        window.set_input_callback(Box::new(CharQueue(chars.clone())));
+
+        Self {
+            window,
+            chars,
+            held: Vec::new(),
+            cursor: None,
+        }
+    }
+}
+
+impl Backend for MinifbBackend {
+    fn present(&mut self, buffer: &[u32], width: usize, height: usize) {
+        self.window
+            .update_with_buffer(buffer, width, height)
+            .unwrap();
+    }
+
+    fn is_open(&self) -> bool {
+        self.window.is_open()
+    }
+
+    fn is_active(&mut self) -> bool {
+        self.window.is_active()
+    }
+
+    fn get_size(&self) -> (usize, usize) {
+        self.window.get_size()
+    }
+
+    fn set_target_fps(&mut self, fps: usize) {
+        self.window.set_target_fps(fps);
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.window.set_title(title);
+    }
+
+    fn set_position(&mut self, x: isize, y: isize) {
+        self.window.set_position(x, y);
+    }
+
+    fn get_position(&self) -> (isize, isize) {
+        self.window.get_position()
+    }
+
+    fn topmost(&mut self, topmost: bool) {
+        self.window.topmost(topmost);
+    }
+
+    fn set_cursor_visibility(&mut self, visible: bool) {
+        self.window.set_cursor_visibility(visible);
+    }
+
+    fn poll_events(&mut self, events: &mut Vec<InputEvent>) {
+        let focused = self.window.is_active();
+        let mut down: Vec<Key> = Vec::new();
+        if focused {
+            down.extend(self.window.get_keys().into_iter().filter_map(map_minifb_key));
+            for (button, key) in [
+                (MouseButton::Left, Key::MouseLeft),
+                (MouseButton::Right, Key::MouseRight),
+                (MouseButton::Middle, Key::MouseMiddle),
+            ] {
+                if self.window.get_mouse_down(button) {
+                    down.push(key);
+                }
+            }
+        }
+        diff_focused_keys(&mut self.held, focused, down, events);
+
+        // The window drops events while unfocused, so the cursor and typed
+        // characters are reported afresh once focus returns.
+        if !focused {
+            self.cursor = None;
+            self.chars.take();
+            return;
+        }
+
+        if let Some((x, y)) = self.window.get_mouse_pos(MouseMode::Pass) {
+            let cursor = (x.floor() as i32, y.floor() as i32);
+            if self.cursor != Some(cursor) {
+                self.cursor = Some(cursor);
+                events.push(InputEvent::CursorMoved(cursor.0, cursor.1));
+            }
+        }
+
+        if let Some((_, y)) = self.window.get_scroll_wheel()
+            && y != 0.0
+        {
+            events.push(InputEvent::Scroll(y.round() as i32));
+        }
+
+        events.extend(self.chars.take().into_iter().map(InputEvent::Char));
+    }
+}
+
+// Helper: map minifb keys to the engine's Key enum
+fn map_minifb_key(key: MfKey) -> Option<Key> {
+    Some(match key {
+        MfKey::A => Key::Char('a'),
+        MfKey::B => Key::Char('b'),
+        MfKey::C => Key::Char('c'),
+        MfKey::D => Key::Char('d'),
+        MfKey::E => Key::Char('e'),
+        MfKey::F => Key::Char('f'),
+        MfKey::G => Key::Char('g'),
+        MfKey::H => Key::Char('h'),
+        MfKey::I => Key::Char('i'),
+        MfKey::J => Key::Char('j'),
+        MfKey::K => Key::Char('k'),
+        MfKey::L => Key::Char('l'),
+        MfKey::M => Key::Char('m'),
+        MfKey::N => Key::Char('n'),
+        MfKey::O => Key::Char('o'),
+        MfKey::P => Key::Char('p'),
+        MfKey::Q => Key::Char('q'),
+        MfKey::R => Key::Char('r'),
+        MfKey::S => Key::Char('s'),
+        MfKey::T => Key::Char('t'),
+        MfKey::U => Key::Char('u'),
+        MfKey::V => Key::Char('v'),
+        MfKey::W => Key::Char('w'),
+        MfKey::X => Key::Char('x'),
+        MfKey::Y => Key::Char('y'),
+        MfKey::Z => Key::Char('z'),
+
+        MfKey::Key0 => Key::Num(0),
+        MfKey::Key1 => Key::Num(1),
+        MfKey::Key2 => Key::Num(2),
+        MfKey::Key3 => Key::Num(3),
+        MfKey::Key4 => Key::Num(4),
+        MfKey::Key5 => Key::Num(5),
+        MfKey::Key6 => Key::Num(6),
+        MfKey::Key7 => Key::Num(7),
+        MfKey::Key8 => Key::Num(8),
+        MfKey::Key9 => Key::Num(9),
+
+        MfKey::F1 => Key::F(1),
+        MfKey::F2 => Key::F(2),
+        MfKey::F3 => Key::F(3),
+        MfKey::F4 => Key::F(4),
+        MfKey::F5 => Key::F(5),
+        MfKey::F6 => Key::F(6),
+        MfKey::F7 => Key::F(7),
+        MfKey::F8 => Key::F(8),
+        MfKey::F9 => Key::F(9),
+        MfKey::F10 => Key::F(10),
+        MfKey::F11 => Key::F(11),
+        MfKey::F12 => Key::F(12),
+
+        MfKey::Escape => Key::Escape,
+        MfKey::Backspace => Key::Backspace,
+        MfKey::Tab => Key::Tab,
+        MfKey::Enter | MfKey::NumPadEnter => Key::Enter,
+        MfKey::Space => Key::Space,
+        MfKey::LeftCtrl => Key::LeftCtrl,
+        MfKey::RightCtrl => Key::RightCtrl,
+        MfKey::LeftShift => Key::LeftShift,
+        MfKey::RightShift => Key::RightShift,
+        MfKey::Up => Key::Up,
+        MfKey::Down => Key::Down,
+        MfKey::Left => Key::Left,
+        MfKey::Right => Key::Right,
+
+        _ => return None,
+    })
+}
+
+/// In-memory backend that needs no display.
+///
+/// Presented frames are only counted; read them back with
+/// [`super::Window::frame`]. The target reports itself as focused and
+/// never throttles, so game loops run as fast as they are driven.
+///
+/// By default the target stays open forever. Use [`HeadlessBackend::frame_limit`]
+/// to close it after a fixed number of frames so that `while window.is_open()`
+/// loops terminate on their own.
+pub struct HeadlessBackend {
+    width: usize,
+    height: usize,
+    frames: usize,
+    frame_limit: Option<usize>,
+}
+
+impl HeadlessBackend {
+    /// Creates a headless target of the given size.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            frames: 0,
+            frame_limit: None,
+        }
+    }
+
+    /// Closes the target once `frames` frames have been presented.
+    pub fn frame_limit(mut self, frames: usize) -> Self {
+        self.frame_limit = Some(frames);
+        self
+    }
+
+    /// Returns the number of frames presented so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn present(&mut self, _buffer: &[u32], width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.frames += 1;
+    }
+
+    fn is_open(&self) -> bool {
+        self.frame_limit.is_none_or(|limit| self.frames < limit)
+    }
+
+    fn is_active(&mut self) -> bool {
+        true
+    }
+
+    fn get_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+}
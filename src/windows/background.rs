@@ -11,7 +11,8 @@ impl super::Window {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
+    /// # let mut window = carose::Window::new("Example", 800, 600);
     /// window.set_background_color(0xFF0000FF); // Sets background to red
     /// ```
     pub fn set_background_color(&mut self, color: u32) {
@@ -30,7 +31,8 @@ impl super::Window {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
+    /// # let mut window = carose::Window::new("Example", 800, 600);
     /// window.set_background_image("assets/background.png");
     /// ```
     pub fn set_background_image(&mut self, path: &str) {
@@ -0,0 +1,238 @@
+use crate::sprites::{SpriteId, SpriteStore};
+use super::Layer;
+
+/// Viewport into the game world.
+///
+/// Sprites and text in layers below [`Layer::HUD`] are positioned in world
+/// coordinates and drawn relative to the camera. HUD and overlay layers are
+/// always drawn in screen coordinates, unaffected by the camera.
+///
+/// The camera is updated once per frame during [`super::Window::draw`]:
+/// it follows its target, is clamped to its bounds, and applies any active
+/// screen shake.
+pub struct Camera {
+    /// World coordinates of the top-left corner of the view.
+    pub position: (f32, f32),
+
+    /// Integer magnification applied to world layers. `1` is unscaled.
+    pub zoom: usize,
+
+    /// Sprite the camera keeps in view, if any.
+    pub target: Option<SpriteId>,
+
+    /// Size of the area around the view center, in world units, in which
+    /// the target can move without the camera following.
+    ///
+    /// `(0.0, 0.0)` keeps the target exactly centered.
+    pub deadzone: (f32, f32),
+
+    /// World rectangle `(x, y, width, height)` the view must stay inside.
+    pub bounds: Option<(f32, f32, f32, f32)>,
+
+    shake_intensity: f32,
+    shake_frames: u32,
+    shake_remaining: u32,
+    shake_offset: (i32, i32),
+    seed: u32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Camera {
+    /// Creates a camera at the world origin with no zoom, target or bounds.
+    pub fn new() -> Self {
+        Self {
+            position: (0.0, 0.0),
+            zoom: 1,
+            target: None,
+            deadzone: (0.0, 0.0),
+            bounds: None,
+            shake_intensity: 0.0,
+            shake_frames: 0,
+            shake_remaining: 0,
+            shake_offset: (0, 0),
+            seed: 0x9E37_79B9,
+        }
+    }
+
+    /// Makes the camera follow a sprite.
+    pub fn follow(&mut self, target: SpriteId) {
+        self.target = Some(target);
+    }
+
+    /// Stops following the current target. The camera stays where it is.
+    pub fn stop_following(&mut self) {
+        self.target = None;
+    }
+
+    /// Restricts the view to a world rectangle.
+    pub fn set_bounds(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.bounds = Some((x, y, width, height));
+    }
+
+    /// Shakes the view for a number of frames.
+    ///
+    /// The offset starts at up to `intensity` pixels in each direction and
+    /// decays linearly to zero. Shaking is deterministic for a given
+    /// sequence of calls, so it does not disturb replays or snapshots.
+    pub fn shake(&mut self, intensity: f32, frames: u32) {
+        self.shake_intensity = intensity;
+        self.shake_frames = frames;
+        self.shake_remaining = frames;
+    }
+
+    /// Returns the effective zoom, never less than `1`.
+    fn scale(&self) -> usize {
+        self.zoom.max(1)
+    }
+
+    /// Converts a world position to screen coordinates.
+    pub fn world_to_screen(&self, world: (f32, f32)) -> (f32, f32) {
+        let zoom = self.scale() as f32;
+        (
+            (world.0 - self.position.0) * zoom + self.shake_offset.0 as f32,
+            (world.1 - self.position.1) * zoom + self.shake_offset.1 as f32,
+        )
+    }
+
+    /// Converts a screen position to world coordinates.
+    pub fn screen_to_world(&self, screen: (f32, f32)) -> (f32, f32) {
+        let zoom = self.scale() as f32;
+        (
+            (screen.0 - self.shake_offset.0 as f32) / zoom + self.position.0,
+            (screen.1 - self.shake_offset.1 as f32) / zoom + self.position.1,
+        )
+    }
+
+    /// Advances the camera by one frame for a view of `width` x `height` pixels.
+    pub(crate) fn update(&mut self, sprites: &SpriteStore, width: usize, height: usize) {
+        let zoom = self.scale() as f32;
+        let view = (width as f32 / zoom, height as f32 / zoom);
+
+        // --- Follow target ---
+        if let Some(sprite) = self.target.and_then(|id| sprites.get(id)) {
+            let target = (
+                sprite.position.0 + sprite.size.0 as f32 / 2.0,
+                sprite.position.1 + sprite.size.1 as f32 / 2.0,
+            );
+            self.position.0 = follow_axis(self.position.0, view.0, self.deadzone.0, target.0);
+            self.position.1 = follow_axis(self.position.1, view.1, self.deadzone.1, target.1);
+        }
+
+        // --- Clamp to bounds ---
+        if let Some((bx, by, bw, bh)) = self.bounds {
+            self.position.0 = clamp_axis(self.position.0, view.0, bx, bw);
+            self.position.1 = clamp_axis(self.position.1, view.1, by, bh);
+        }
+
+        // --- Screen shake ---
+        if self.shake_remaining > 0 {
+            let strength = self.shake_intensity * self.shake_remaining as f32 / self.shake_frames as f32;
+            let dx = self.next_unit() * strength;
+            let dy = self.next_unit() * strength;
+            self.shake_offset = (dx.round() as i32, dy.round() as i32);
+            self.shake_remaining -= 1;
+        } else {
+            self.shake_offset = (0, 0);
+        }
+    }
+
+    /// Returns the transform used to draw items in `layer`.
+    pub(crate) fn view_for(&self, layer: Layer) -> View {
+        if layer >= Layer::HUD {
+            return View::SCREEN;
+        }
+
+        let zoom = self.scale();
+        View {
+            origin: (
+                -self.position.0 * zoom as f32 + self.shake_offset.0 as f32,
+                -self.position.1 * zoom as f32 + self.shake_offset.1 as f32,
+            ),
+            zoom,
+        }
+    }
+
+    /// Returns the part of `layer` shown in a `width` x `height` frame as
+    /// `(x, y, width, height)` in layer coordinates.
+    ///
+    /// Screen shake is ignored, so the area does not jitter while shaking.
+    pub(crate) fn visible_area(&self, layer: Layer, width: usize, height: usize) -> (f32, f32, f32, f32) {
+        if layer >= Layer::HUD {
+            return (0.0, 0.0, width as f32, height as f32);
+        }
+
+        let zoom = self.scale() as f32;
+        (self.position.0, self.position.1, width as f32 / zoom, height as f32 / zoom)
+    }
+
+    /// Returns a pseudo-random value in `-1.0..=1.0` (xorshift32).
+    fn next_unit(&mut self) -> f32 {
+        let mut x = self.seed;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.seed = x;
+        (x as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+}
+
+/// Transform from layer coordinates to screen pixels.
+#[derive(Clone, Copy)]
+pub(crate) struct View {
+    /// Screen position of the layer origin.
+    pub(crate) origin: (f32, f32),
+
+    /// Integer magnification.
+    pub(crate) zoom: usize,
+}
+
+impl View {
+    /// Identity transform used for screen-space layers.
+    pub(crate) const SCREEN: View = View { origin: (0.0, 0.0), zoom: 1 };
+
+    /// Converts a layer position to the screen pixel it starts on.
+    pub(crate) fn apply(&self, position: (f32, f32)) -> (i32, i32) {
+        (
+            (self.origin.0 + position.0 * self.zoom as f32).floor() as i32,
+            (self.origin.1 + position.1 * self.zoom as f32).floor() as i32,
+        )
+    }
+
+    /// Converts a screen position back to layer coordinates.
+    pub(crate) fn invert(&self, screen: (f32, f32)) -> (f32, f32) {
+        (
+            (screen.0 - self.origin.0) / self.zoom as f32,
+            (screen.1 - self.origin.1) / self.zoom as f32,
+        )
+    }
+}
+
+/// Moves one axis of the view so `target` stays inside the deadzone.
+fn follow_axis(position: f32, view: f32, deadzone: f32, target: f32) -> f32 {
+    let center = position + view / 2.0;
+    let half = deadzone / 2.0;
+
+    if target < center - half {
+        target + half - view / 2.0
+    } else if target > center + half {
+        target - half - view / 2.0
+    } else {
+        position
+    }
+}
+
+/// Keeps one axis of the view inside `[start, start + length]`.
+///
+/// If the bounds are smaller than the view, the view is centered on them.
+fn clamp_axis(position: f32, view: f32, start: f32, length: f32) -> f32 {
+    if length <= view {
+        start + (length - view) / 2.0
+    } else {
+        position.clamp(start, start + length - view)
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::Path;
+use crate::image::save_image;
+
+
+impl super::Window {
+    /// Returns the most recently composed frame.
+    ///
+    /// The slice holds one `0xRRGGBB` pixel per entry in row-major order,
+    /// with dimensions given by [`Self::frame_size`]. It is empty until
+    /// [`Self::draw`] has been called at least once.
+    pub fn frame(&self) -> &[u32] {
+        self.frame.pixels()
+    }
+
+    /// Returns the `(width, height)` of the most recently composed frame.
+    pub fn frame_size(&self) -> (usize, usize) {
+        self.frame.size()
+    }
+
+    /// Returns a single pixel of the most recently composed frame.
+    ///
+    /// Returns `None` if the coordinates are outside the frame or no frame
+    /// has been drawn yet.
+    pub fn frame_pixel(&self, x: usize, y: usize) -> Option<u32> {
+        let (width, height) = self.frame.size();
+        if x >= width || y >= height {
+            return None;
+        }
+        self.frame.pixels().get(y * width + x).copied()
+    }
+
+    /// Saves the most recently composed frame as an image file.
+    ///
+    /// The format is chosen from the file extension, so `.png` and `.bmp`
+    /// both work.
+    ///
+    /// # Errors
+    /// Returns an `image::ImageError` if no frame has been drawn yet or the
+    /// file cannot be encoded or written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut window = carose::Window::headless(320, 240);
+    /// window.draw();
+    /// window.save_frame("screenshot.png").expect("Failed to save screenshot");
+    /// ```
+    pub fn save_frame<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        let (width, height) = self.frame.size();
+        save_image(path, self.frame.pixels(), width, height)
+    }
+}
@@ -0,0 +1,160 @@
+use std::time::{Duration, Instant};
+
+/// How often the measured FPS is refreshed.
+const FPS_SAMPLE: Duration = Duration::from_millis(500);
+
+/// Frame timing for a window.
+///
+/// The clock ticks once at the end of every [`super::Window::draw`] and
+/// measures the time between consecutive frames. Game logic should scale
+/// movement by [`Clock::delta_secs`] (or use [`super::Window::run`]) so that
+/// game speed does not depend on the frame rate.
+///
+/// A clock can also run on a fixed delta, in which case every frame reports
+/// the same duration regardless of wall-clock time. Headless windows use
+/// this so that tests and replays are deterministic.
+pub struct Clock {
+    last: Instant,
+    delta: Duration,
+    elapsed: Duration,
+    frame: u64,
+    fps: f32,
+    sample_time: Duration,
+    sample_frames: u32,
+    fixed_delta: Option<Duration>,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock {
+    /// Creates a clock that measures wall-clock time.
+    pub fn new() -> Self {
+        Self {
+            last: Instant::now(),
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            frame: 0,
+            fps: 0.0,
+            sample_time: Duration::ZERO,
+            sample_frames: 0,
+            fixed_delta: None,
+        }
+    }
+
+    /// Creates a clock where every frame lasts exactly `delta`.
+    pub fn fixed(delta: Duration) -> Self {
+        let mut clock = Self::new();
+        clock.fixed_delta = Some(delta);
+        clock
+    }
+
+    /// Switches between a fixed frame duration (`Some`) and wall-clock time (`None`).
+    pub fn set_fixed_delta(&mut self, delta: Option<Duration>) {
+        self.fixed_delta = delta;
+        self.last = Instant::now();
+    }
+
+    /// Marks the end of a frame.
+    pub(crate) fn tick(&mut self) {
+        let now = Instant::now();
+        self.delta = self.fixed_delta.unwrap_or_else(|| now - self.last);
+        self.last = now;
+        self.elapsed += self.delta;
+        self.frame += 1;
+
+        self.sample_time += self.delta;
+        self.sample_frames += 1;
+        if self.sample_time >= FPS_SAMPLE {
+            self.fps = self.sample_frames as f32 / self.sample_time.as_secs_f32();
+            self.sample_time = Duration::ZERO;
+            self.sample_frames = 0;
+        }
+    }
+
+    /// Replaces the duration of the last frame, adjusting the elapsed time
+    /// to match.
+    pub(crate) fn replace_delta(&mut self, delta: Duration) {
+        self.elapsed = self.elapsed.saturating_sub(self.delta) + delta;
+        self.delta = delta;
+    }
+
+    /// Returns the duration of the last frame.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Returns the duration of the last frame in seconds.
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Returns the total time accumulated over all frames.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of frames drawn so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Returns the measured frames per second.
+    ///
+    /// The value is averaged over roughly half a second and is `0.0`
+    /// until the first sample completes.
+    pub fn fps(&self) -> f32 {
+        self.fps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(20);
+
+    #[test]
+    fn fixed_delta_ignores_wall_clock() {
+        let mut clock = Clock::fixed(FRAME);
+        for _ in 0..3 {
+            clock.tick();
+        }
+        assert_eq!(clock.delta(), FRAME);
+        assert_eq!(clock.elapsed(), FRAME * 3);
+        assert_eq!(clock.frame(), 3);
+        assert_eq!(clock.fps(), 0.0);
+
+        for _ in 0..22 {
+            clock.tick();
+        }
+        assert!((clock.fps() - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn replace_delta_adjusts_elapsed() {
+        let mut clock = Clock::fixed(FRAME);
+        clock.tick();
+        clock.tick();
+
+        clock.replace_delta(Duration::from_millis(5));
+        assert_eq!(clock.delta(), Duration::from_millis(5));
+        assert_eq!(clock.elapsed(), Duration::from_millis(25));
+        assert_eq!(clock.frame(), 2);
+
+        clock.tick();
+        assert_eq!(clock.delta(), FRAME);
+        assert_eq!(clock.elapsed(), Duration::from_millis(45));
+    }
+
+    #[test]
+    fn fixed_delta_can_be_switched_off() {
+        let mut clock = Clock::fixed(Duration::from_secs(10));
+        clock.set_fixed_delta(None);
+        clock.tick();
+        assert!(clock.delta() < Duration::from_secs(10));
+    }
+}
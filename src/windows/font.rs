@@ -0,0 +1,689 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use crate::image::{load_image_2d, load_sprite_sheet};
+use super::render::FrameBuffer;
+use super::text::{get_font_map, REPLACEMENT_CHAR};
+
+/// Gap between glyphs of the built-in font, in screen pixels.
+const BUILTIN_SPACING: i32 = 7;
+
+/// Reasons a font could not be loaded.
+#[derive(Debug)]
+pub enum FontError {
+    /// The font descriptor could not be read.
+    Io(std::io::Error),
+
+    /// A glyph page image could not be read or decoded.
+    Image(image::ImageError),
+
+    /// The font descriptor is malformed.
+    Parse {
+        /// Line of the descriptor the error was found on, starting at `1`.
+        line: usize,
+
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::Io(err) => write!(f, "io error: {}", err),
+            FontError::Image(err) => write!(f, "image error: {}", err),
+            FontError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+impl From<std::io::Error> for FontError {
+    fn from(err: std::io::Error) -> Self {
+        FontError::Io(err)
+    }
+}
+
+impl From<image::ImageError> for FontError {
+    fn from(err: image::ImageError) -> Self {
+        FontError::Image(err)
+    }
+}
+
+/// A horizontal run of pixels with equal coverage: `(row, column, length, coverage)`.
+type GlyphRun = (usize, usize, usize, u8);
+
+/// A single character of a [`Font`].
+///
+/// Glyphs are coverage masks: each pixel stores how strongly it is covered
+/// (`0` to `255`), and the text color is blended in with that strength.
+#[derive(Clone, Debug)]
+pub struct Glyph {
+    width: usize,
+    height: usize,
+    coverage: Vec<u8>,
+    offset: (i32, i32),
+    advance: i32,
+    runs: Vec<GlyphRun>,
+}
+
+impl Glyph {
+    /// Creates a glyph from a row-major coverage mask.
+    ///
+    /// # Parameters
+    /// - `width`, `height`: Size of the mask in font pixels.
+    /// - `coverage`: `width * height` coverage values.
+    /// - `offset`: Position of the mask relative to the pen and the top of the line.
+    /// - `advance`: Distance the pen moves after this glyph.
+    ///
+    /// # Panics
+    /// Panics if `coverage` does not contain exactly `width * height` values.
+    pub fn new(width: usize, height: usize, coverage: Vec<u8>, offset: (i32, i32), advance: i32) -> Self {
+        assert_eq!(coverage.len(), width * height, "glyph coverage does not match its size");
+
+        let mut runs = Vec::new();
+        for (y, row) in coverage.chunks(width.max(1)).enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let value = row[x];
+                let start = x;
+                while x < row.len() && row[x] == value {
+                    x += 1;
+                }
+                if value != 0 {
+                    runs.push((y, start, x - start, value));
+                }
+            }
+        }
+
+        Self { width, height, coverage, offset, advance, runs }
+    }
+
+    /// Cuts a glyph out of an `0xAARRGGBB` bitmap.
+    ///
+    /// Coverage is the pixel's alpha limited by its brightest channel, so
+    /// both light-on-transparent and light-on-black images work. Parts of
+    /// the rectangle outside the bitmap are left uncovered.
+    fn from_bitmap(
+        bitmap: &[Vec<u32>],
+        rect: (usize, usize, usize, usize),
+        offset: (i32, i32),
+        advance: i32,
+    ) -> Self {
+        let (x, y, width, height) = rect;
+        let mut coverage = vec![0u8; width * height];
+
+        for gy in 0..height {
+            let Some(row) = bitmap.get(y + gy) else { break };
+            for gx in 0..width {
+                let Some(&pixel) = row.get(x + gx) else { break };
+                let [a, r, g, b] = pixel.to_be_bytes();
+                coverage[gy * width + gx] = a.min(r.max(g).max(b));
+            }
+        }
+
+        Self::new(width, height, coverage, offset, advance)
+    }
+
+    /// Returns the size of the coverage mask.
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the row-major coverage mask.
+    pub fn coverage(&self) -> &[u8] {
+        &self.coverage
+    }
+
+    /// Returns the position of the mask relative to the pen and line top.
+    pub fn offset(&self) -> (i32, i32) {
+        self.offset
+    }
+
+    /// Returns the distance the pen moves after this glyph.
+    pub fn advance(&self) -> i32 {
+        self.advance
+    }
+}
+
+/// A bitmap font used to draw text items.
+///
+/// All metrics are in font pixels and are multiplied by the text size when
+/// drawn, except [`Font::spacing`], which is added in screen pixels.
+///
+/// Characters missing from a font are drawn with its [`REPLACEMENT_CHAR`]
+/// glyph, or `?` if it has none, or skipped.
+///
+/// # Example
+/// ```no_run
+/// use carose::Font;
+/// # fn main() -> Result<(), carose::windows::font::FontError> {
+/// # let mut window = carose::Window::new("Example", 800, 600);
+/// window.add_font("title", Font::load_bmfont("assets/fonts/title.fnt")?);
+/// window.show_text("title", "Carose", (20, 20), 1, 0xFFFFFF, carose::TextAlign::Left);
+/// window.set_text_font("title", Some("title"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Font {
+    glyphs: HashMap<char, Glyph>,
+    kerning: HashMap<(char, char), i32>,
+
+    /// Height of a line of text in font pixels.
+    pub line_height: usize,
+
+    /// Extra space between glyphs in screen pixels, not scaled by size.
+    pub spacing: i32,
+}
+
+impl Font {
+    /// Creates an empty font with the given line height.
+    pub fn new(line_height: usize) -> Self {
+        Self { line_height, ..Self::default() }
+    }
+
+    /// Returns the built-in 5x5 font, built once on first use.
+    pub fn builtin() -> &'static Font {
+        static BUILTIN: OnceLock<Font> = OnceLock::new();
+
+        BUILTIN.get_or_init(|| {
+            let mut font = Font::new(5);
+            font.spacing = BUILTIN_SPACING;
+            for (c, cells) in get_font_map() {
+                let coverage = cells.iter().flatten().map(|&cell| if cell != 0 { 255 } else { 0 }).collect();
+                font.insert_glyph(c, Glyph::new(5, 5, coverage, (0, 0), 5));
+            }
+            font
+        })
+    }
+
+    /// Loads a font from a monospaced glyph sheet.
+    ///
+    /// The sheet is sliced into cells with
+    /// [`load_sprite_sheet`](crate::image::load_sprite_sheet), and the
+    /// characters of `chars` are assigned to the cells left-to-right,
+    /// top-to-bottom. Every glyph advances by `glyph_width`.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the sheet image.
+    /// - `glyph_width`, `glyph_height`: Size of one cell in pixels.
+    /// - `chars`: Characters in the order they appear on the sheet.
+    ///
+    /// # Errors
+    /// Returns [`FontError::Image`] if the image cannot be loaded or decoded.
+    ///
+    /// # Notes
+    /// - Extra characters beyond the number of cells are ignored.
+    /// - Glyphs should be drawn light on a dark or transparent background.
+    pub fn from_sheet<P: AsRef<Path>>(
+        path: P,
+        glyph_width: u32,
+        glyph_height: u32,
+        chars: &str,
+    ) -> Result<Font, FontError> {
+        let cells = load_sprite_sheet(path, glyph_width, glyph_height)?;
+        let (width, height) = (glyph_width as usize, glyph_height as usize);
+
+        let mut font = Font::new(height);
+        for (c, cell) in chars.chars().zip(&cells) {
+            font.insert_glyph(c, Glyph::from_bitmap(cell, (0, 0, width, height), (0, 0), width as i32));
+        }
+        Ok(font)
+    }
+
+    /// Loads a font from an AngelCode BMFont descriptor.
+    ///
+    /// Both the text and the XML descriptor formats are supported. Page
+    /// images are loaded relative to the descriptor's directory. A glyph
+    /// with id `-1` is used as the replacement glyph.
+    ///
+    /// # Errors
+    /// Returns a [`FontError`] if the descriptor cannot be read or parsed,
+    /// or if a page image cannot be loaded.
+    pub fn load_bmfont<P: AsRef<Path>>(path: P) -> Result<Font, FontError> {
+        let path = path.as_ref();
+        let descriptor = fs::read_to_string(path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        let mut font = Font::new(0);
+        let mut pages: HashMap<u32, Vec<Vec<u32>>> = HashMap::new();
+        let mut chars = Vec::new();
+
+        for element in parse_elements(&descriptor) {
+            match element.tag.as_str() {
+                "common" => font.line_height = element.attr("lineHeight")?,
+                "page" => {
+                    let id: u32 = element.attr("id")?;
+                    let file: String = element.attr("file")?;
+                    pages.insert(id, load_image_2d(dir.join(file))?);
+                }
+                "char" => chars.push(element),
+                "kerning" => {
+                    let first = element.char_attr("first")?;
+                    let second = element.char_attr("second")?;
+                    font.set_kerning(first, second, element.attr("amount")?);
+                }
+                _ => {}
+            }
+        }
+
+        for element in chars {
+            let c = element.char_attr("id")?;
+            let page: u32 = element.attr_or("page", 0)?;
+            let Some(bitmap) = pages.get(&page) else {
+                return Err(element.error(format!("page {} is not defined", page)));
+            };
+
+            let glyph = Glyph::from_bitmap(
+                bitmap,
+                (element.attr("x")?, element.attr("y")?, element.attr("width")?, element.attr("height")?),
+                (element.attr_or("xoffset", 0)?, element.attr_or("yoffset", 0)?),
+                element.attr("xadvance")?,
+            );
+            font.insert_glyph(c, glyph);
+        }
+
+        Ok(font)
+    }
+
+    /// Adds or replaces the glyph for a character.
+    pub fn insert_glyph(&mut self, c: char, glyph: Glyph) {
+        self.glyphs.insert(c, glyph);
+    }
+
+    /// Returns the glyph for a character, if the font has one.
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&c)
+    }
+
+    /// Sets the extra advance, in font pixels, between a pair of characters.
+    pub fn set_kerning(&mut self, first: char, second: char, amount: i32) {
+        if amount == 0 {
+            self.kerning.remove(&(first, second));
+        } else {
+            self.kerning.insert((first, second), amount);
+        }
+    }
+
+    /// Returns the extra advance between a pair of characters.
+    pub fn kerning(&self, first: char, second: char) -> i32 {
+        self.kerning.get(&(first, second)).copied().unwrap_or(0)
+    }
+
+    /// Returns the size in pixels of `content` drawn at `size`.
+    ///
+    /// The width is the distance from the start of the first glyph to the
+    /// end of the last glyph's advance. Empty text measures `(0, 0)`.
+    pub fn measure(&self, content: &str, size: usize) -> (usize, usize) {
+        if content.is_empty() {
+            return (0, 0);
+        }
+        let width = self.place(content.chars().map(|c| (c, 0)), size, |_, _, _| {});
+        (width.max(0) as usize, self.line_height * size)
+    }
+
+    /// Returns the width in pixels of a line of colored characters.
+    pub(crate) fn line_width(&self, line: &[(char, u32)], size: usize) -> usize {
+        self.place(line.iter().copied(), size, |_, _, _| {}).max(0) as usize
+    }
+
+    /// Draws a line of characters, each in its own color, with its
+    /// top-left corner at `(x, y)`.
+    pub(crate) fn draw_line(
+        &self,
+        frame: &mut FrameBuffer,
+        line: impl IntoIterator<Item = (char, u32)>,
+        x: i32,
+        y: i32,
+        size: usize,
+    ) {
+        let scale = size as i32;
+        self.place(line, size, |pen, glyph, color| {
+            let gx = x + pen + glyph.offset.0 * scale;
+            let gy = y + glyph.offset.1 * scale;
+            for &(row, col, len, coverage) in &glyph.runs {
+                frame.blend_rect(
+                    gx + (col * size) as i32,
+                    gy + (row * size) as i32,
+                    len * size,
+                    size,
+                    color,
+                    coverage,
+                );
+            }
+        });
+    }
+
+    /// Returns the glyph drawn for `c`, falling back to the replacement glyph.
+    fn resolve(&self, c: char) -> Option<&Glyph> {
+        self.glyphs
+            .get(&c)
+            .or_else(|| self.glyphs.get(&REPLACEMENT_CHAR))
+            .or_else(|| self.glyphs.get(&'?'))
+    }
+
+    /// Walks the glyphs of a line, calling `visit` with each glyph, its pen
+    /// position and its color, and returns the total width.
+    fn place<'a>(
+        &'a self,
+        line: impl IntoIterator<Item = (char, u32)>,
+        size: usize,
+        mut visit: impl FnMut(i32, &'a Glyph, u32),
+    ) -> i32 {
+        let scale = size as i32;
+        let mut pen = 0;
+        let mut previous = None;
+
+        for (c, color) in line {
+            let Some(glyph) = self.resolve(c) else { continue };
+            if let Some(previous) = previous {
+                pen += self.spacing + self.kerning(previous, c) * scale;
+            }
+            visit(pen, glyph, color);
+            pen += glyph.advance * scale;
+            previous = Some(c);
+        }
+        pen
+    }
+}
+
+/// A tag and its attributes from a BMFont descriptor.
+struct Element {
+    line: usize,
+    tag: String,
+    attrs: HashMap<String, String>,
+}
+
+impl Element {
+    fn error(&self, message: String) -> FontError {
+        FontError::Parse { line: self.line, message }
+    }
+
+    /// Returns a required attribute parsed as `T`.
+    fn attr<T: FromStr>(&self, key: &str) -> Result<T, FontError> {
+        let Some(value) = self.attrs.get(key) else {
+            return Err(self.error(format!("<{}> is missing `{}`", self.tag, key)));
+        };
+        value
+            .parse()
+            .map_err(|_| self.error(format!("invalid `{}` value `{}`", key, value)))
+    }
+
+    /// Returns an optional attribute parsed as `T`, or `default`.
+    fn attr_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, FontError> {
+        if self.attrs.contains_key(key) { self.attr(key) } else { Ok(default) }
+    }
+
+    /// Returns a required character id attribute. `-1` is the replacement glyph.
+    fn char_attr(&self, key: &str) -> Result<char, FontError> {
+        let id: i64 = self.attr(key)?;
+        if id == -1 {
+            return Ok(REPLACEMENT_CHAR);
+        }
+        u32::try_from(id)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(format!("invalid character id {}", id)))
+    }
+}
+
+/// Splits a BMFont descriptor in text or XML format into elements.
+fn parse_elements(descriptor: &str) -> Vec<Element> {
+    let mut elements = Vec::new();
+
+    if descriptor.trim_start().starts_with('<') {
+        let mut next_line = 1;
+        for segment in descriptor.split('<') {
+            let line = next_line;
+            next_line += segment.matches('\n').count();
+
+            if segment.starts_with(['?', '!', '/']) {
+                continue;
+            }
+            let Some(end) = segment.find('>') else { continue };
+            let body = segment[..end].trim_end_matches('/');
+            if let Some(element) = parse_element(line, body) {
+                elements.push(element);
+            }
+        }
+    } else {
+        for (i, body) in descriptor.lines().enumerate() {
+            if let Some(element) = parse_element(i + 1, body) {
+                elements.push(element);
+            }
+        }
+    }
+
+    elements
+}
+
+/// Parses `tag key=value key="quoted value" ...`.
+fn parse_element(line: usize, body: &str) -> Option<Element> {
+    let body = body.trim();
+    let tag_end = body.find(char::is_whitespace).unwrap_or(body.len());
+    let tag = &body[..tag_end];
+    if tag.is_empty() {
+        return None;
+    }
+
+    let mut attrs = HashMap::new();
+    let mut rest = body[tag_end..].trim_start();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let after = rest[eq + 1..].trim_start();
+
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+            }
+            None => {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            }
+        };
+
+        // A key may be preceded by attribute-less words; keep only the last one.
+        let key = key.rsplit(char::is_whitespace).next().unwrap_or(key);
+        attrs.insert(key.to_string(), value.to_string());
+        rest = remaining.trim_start();
+    }
+
+    Some(Element { line, tag: tag.to_string(), attrs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::save_image;
+    use std::path::PathBuf;
+
+    /// Writes `descriptor` and an 8x4 page image into a fresh directory and
+    /// returns the descriptor path.
+    ///
+    /// The page is white where glyphs are set: a 2x3 block at x 0..2, a
+    /// 3x3 block at x 2..5 and a 3x4 block at x 5..8.
+    fn write_font(name: &str, descriptor: &str) -> PathBuf {
+        let dir = crate::testing::scratch_dir(&format!("font-{}", name));
+
+        let mut page = vec![0u32; 8 * 4];
+        for y in 0..4 {
+            for x in 0..8 {
+                if y < 3 || x >= 5 {
+                    page[y * 8 + x] = 0xFFFFFF;
+                }
+            }
+        }
+        save_image(dir.join("page 0.png"), &page, 8, 4).unwrap();
+
+        let path = dir.join("font.fnt");
+        fs::write(&path, descriptor).unwrap();
+        path
+    }
+
+    const TEXT: &str = "\
+info face=\"Tiny Font\" size=4 bold=0
+common lineHeight=5 base=4 pages=1
+page id=0 file=\"page 0.png\"
+chars count=3
+char id=65 x=0 y=0 width=2 height=3 xoffset=1 yoffset=1 xadvance=4 page=0
+char id=66 x=2 y=0 width=3 height=3 xadvance=3
+char id=-1 x=5 y=0 width=3 height=4 xadvance=3
+kernings count=1
+kerning first=65 second=66 amount=-2
+";
+
+    const XML: &str = r#"<?xml version="1.0"?>
+<font>
+  <info face="Tiny Font" size="4"/>
+  <common lineHeight="5" base="4" pages="1"/>
+  <pages>
+    <page id="0" file="page 0.png"/>
+  </pages>
+  <chars count="3">
+    <char id="65" x="0" y="0" width="2" height="3" xoffset="1" yoffset="1" xadvance="4" page="0"/>
+    <char id="66" x="2" y="0" width="3" height="3" xadvance="3"/>
+    <char id="-1" x="5" y="0" width="3" height="4" xadvance="3"/>
+  </chars>
+  <kernings count="1">
+    <kerning first="65" second="66" amount="-2"/>
+  </kernings>
+</font>
+"#;
+
+    fn check_tiny_font(font: &Font) {
+        assert_eq!(font.line_height, 5);
+
+        let a = font.glyph('A').unwrap();
+        assert_eq!(a.size(), (2, 3));
+        assert_eq!(a.offset(), (1, 1));
+        assert_eq!(a.advance(), 4);
+        assert!(a.coverage().iter().all(|&c| c == 255));
+
+        let b = font.glyph('B').unwrap();
+        assert_eq!(b.offset(), (0, 0));
+        assert_eq!(b.advance(), 3);
+
+        let replacement = font.glyph(REPLACEMENT_CHAR).unwrap();
+        assert_eq!(replacement.size(), (3, 4));
+        assert_eq!(font.kerning('A', 'B'), -2);
+        assert_eq!(font.kerning('B', 'A'), 0);
+    }
+
+    #[test]
+    fn loads_text_descriptor() {
+        let font = Font::load_bmfont(write_font("text", TEXT)).unwrap();
+        check_tiny_font(&font);
+    }
+
+    #[test]
+    fn loads_xml_descriptor() {
+        let font = Font::load_bmfont(write_font("xml", XML)).unwrap();
+        check_tiny_font(&font);
+    }
+
+    #[test]
+    fn quoted_values_keep_spaces() {
+        let element = parse_element(1, r#"info face="Tiny Font" charset="" size=4"#).unwrap();
+        assert_eq!(element.tag, "info");
+        assert_eq!(element.attrs["face"], "Tiny Font");
+        assert_eq!(element.attrs["charset"], "");
+        assert_eq!(element.attrs["size"], "4");
+    }
+
+    #[test]
+    fn missing_attribute_reports_its_line() {
+        let text = TEXT.replace("char id=66 x=2 y=0 width=3 height=3 xadvance=3", "char id=66 x=2 y=0 width=3 height=3");
+        match Font::load_bmfont(write_font("text-missing", &text)) {
+            Err(FontError::Parse { line, message }) => {
+                assert_eq!(line, 6);
+                assert!(message.contains("xadvance"), "{}", message);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        let xml = XML.replace(r#"<common lineHeight="5" base="4" pages="1"/>"#, r#"<common base="4" pages="1"/>"#);
+        match Font::load_bmfont(write_font("xml-missing", &xml)) {
+            Err(FontError::Parse { line, message }) => {
+                assert_eq!(line, 4);
+                assert!(message.contains("lineHeight"), "{}", message);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn replacement_glyph_draws_unknown_characters() {
+        let font = Font::load_bmfont(write_font("replacement", TEXT)).unwrap();
+        assert_eq!(font.measure("?", 1), font.measure("\u{263A}", 1));
+        assert_eq!(font.measure("Z", 2), (6, 10));
+    }
+
+    #[test]
+    fn kerning_and_advance_change_measure() {
+        let mut font = Font::load_bmfont(write_font("measure", TEXT)).unwrap();
+        assert_eq!(font.measure("A", 1), (4, 5));
+        assert_eq!(font.measure("AB", 1), (5, 5));
+        assert_eq!(font.measure("BA", 1), (7, 5));
+        assert_eq!(font.measure("AB", 2), (10, 10));
+
+        font.set_kerning('A', 'B', 0);
+        assert_eq!(font.measure("AB", 1), (7, 5));
+    }
+
+    #[test]
+    fn offsets_move_drawn_glyphs() {
+        let font = Font::load_bmfont(write_font("offset", TEXT)).unwrap();
+        let mut frame = FrameBuffer::new();
+        frame.resize(8, 6);
+        frame.clear(0);
+        font.draw_line(&mut frame, [('A', 0xFFFFFF)], 0, 0, 1);
+
+        let set: Vec<(usize, usize)> = (0..6)
+            .flat_map(|y| (0..8).map(move |x| (x, y)))
+            .filter(|&(x, y)| frame.pixels()[y * 8 + x] != 0)
+            .collect();
+        assert_eq!(set, [(1, 1), (2, 1), (1, 2), (2, 2), (1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn loads_monospaced_sheet() {
+        let path = write_font("sheet", "");
+        let font = Font::from_sheet(path.with_file_name("page 0.png"), 4, 4, "abc").unwrap();
+
+        assert_eq!(font.line_height, 4);
+        assert!(font.glyph('c').is_none());
+        let a = font.glyph('a').unwrap();
+        assert_eq!(a.size(), (4, 4));
+        assert_eq!(a.advance(), 4);
+        assert_eq!(&a.coverage()[..4], &[255; 4]);
+        assert_eq!(&a.coverage()[12..], &[0; 4]);
+        assert_eq!(font.measure("ab", 1), (8, 4));
+
+        match Font::from_sheet(path.with_file_name("missing.png"), 4, 4, "abc") {
+            Err(FontError::Image(_)) => {}
+            other => panic!("expected an image error, got {:?}", other.map(|font| font.line_height)),
+        }
+    }
+
+    #[test]
+    fn show_text_with_font_draws_in_that_font() {
+        let mut window = crate::Window::headless(16, 8);
+        window.add_font("tiny", Font::load_bmfont(write_font("show", TEXT)).unwrap());
+        window.show_text_with_font("a", "A", (0, 0), 1, 0xFFFFFF, crate::TextAlign::Left, "tiny");
+        assert_eq!(window.texts["a"].font.as_deref(), Some("tiny"));
+        window.draw();
+
+        // The tiny 'A' is a 2x3 block offset by (1, 1).
+        let lit: Vec<(usize, usize)> = (0..8)
+            .flat_map(|y| (0..16).map(move |x| (x, y)))
+            .filter(|&(x, y)| window.frame_pixel(x, y) != Some(0))
+            .collect();
+        assert_eq!(lit, [(1, 1), (2, 1), (1, 2), (2, 2), (1, 3), (2, 3)]);
+    }
+}
@@ -0,0 +1,79 @@
+/// Render layer of a sprite or text item.
+///
+/// Everything in a lower layer is drawn before (and therefore underneath)
+/// everything in a higher layer. Within a layer, items are ordered by their
+/// `z_index`. On equal layer and `z_index`, sprites are drawn before text,
+/// sprites in the order they were added and text items in order of their id.
+///
+/// The named constants cover the common cases, but any `i32` is a valid
+/// layer, e.g. `Layer(Layer::WORLD.0 + 1)` for a layer just above the world.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Layer(pub i32);
+
+impl Layer {
+    /// Backdrops and parallax scenery, drawn first.
+    pub const BACKGROUND: Layer = Layer(-100);
+
+    /// Game world: players, enemies, projectiles. Default for sprites.
+    pub const WORLD: Layer = Layer(0);
+
+    /// Heads-up display such as score and health. Default for text.
+    pub const HUD: Layer = Layer(100);
+
+    /// Menus, fades and anything that must cover the HUD.
+    pub const OVERLAY: Layer = Layer(200);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprites::{Sprite, SpriteType};
+    use crate::{TextAlign, Window};
+
+    /// Adds a 4x4 sprite at the origin.
+    fn square(window: &mut Window, color: u32, layer: Layer, z_index: i32) {
+        let mut sprite = Sprite::new_color((0.0, 0.0), (4, 4), SpriteType::Wall, 1, color, false);
+        sprite.layer = layer;
+        sprite.z_index = z_index;
+        window.add_sprite(sprite);
+    }
+
+    fn top_color(window: &mut Window) -> u32 {
+        window.draw();
+        window.frame_pixel(1, 0).unwrap()
+    }
+
+    #[test]
+    fn higher_layers_cover_lower_ones_whatever_their_z_index() {
+        let mut window = Window::headless(8, 8);
+        square(&mut window, 0x0000FF, Layer::HUD, -10);
+        square(&mut window, 0xFF0000, Layer::WORLD, 10);
+        assert_eq!(top_color(&mut window), 0x0000FF);
+    }
+
+    #[test]
+    fn z_index_orders_within_a_layer() {
+        let mut window = Window::headless(8, 8);
+        square(&mut window, 0x00FF00, Layer::WORLD, 2);
+        square(&mut window, 0xFF0000, Layer::WORLD, 1);
+        assert_eq!(top_color(&mut window), 0x00FF00);
+    }
+
+    #[test]
+    fn ties_draw_sprites_in_insertion_order_then_texts_by_id() {
+        let mut window = Window::headless(8, 8);
+        square(&mut window, 0xFF0000, Layer::WORLD, 0);
+        square(&mut window, 0x00FF00, Layer::WORLD, 0);
+        assert_eq!(top_color(&mut window), 0x00FF00);
+
+        // 'A' covers (1, 0); the text is added first but still drawn last.
+        window.show_text("t", "A", (0, 0), 1, 0xFFFFFF, TextAlign::Left);
+        window.set_text_layer("t", Layer::WORLD, 0);
+        square(&mut window, 0x0000FF, Layer::WORLD, 0);
+        assert_eq!(top_color(&mut window), 0xFFFFFF);
+
+        window.show_text("b", "A", (0, 0), 1, 0x00FFFF, TextAlign::Left);
+        window.show_text("a", "A", (0, 0), 1, 0xFF00FF, TextAlign::Left);
+        assert_eq!(top_color(&mut window), 0x00FFFF);
+    }
+}
@@ -1,14 +1,30 @@
 pub mod text;
+pub mod font;
+pub mod text_box;
+pub mod backend;
 mod background;
-use crate::controls::Input;
-
-use text::{
-    get_font_map,
-    TextItem
-};
-use minifb::{Window as MfWindow, WindowOptions};
-use crate::sprites::{SpriteRender, Sprite};
+mod capture;
+mod render;
+mod layer;
+mod camera;
+mod clock;
+mod mouse;
+mod recording;
+mod runner;
+use crate::controls::{Input, InputBackend, InputEvent, InputRecording, InputSource, NativeGamepads, RecordedFrame};
+
+use text::TextItem;
+use font::Font;
+use render::FrameBuffer;
+pub use layer::Layer;
+pub use camera::Camera;
+pub use clock::Clock;
+pub use runner::Game;
+use camera::View;
+use crate::sprites::{Sprite, SpriteRender, SpriteStore};
 use std::collections::HashMap;
+use std::time::Duration;
+pub use backend::{Backend, HeadlessBackend, MinifbBackend};
 
 
 pub enum Background {
@@ -21,13 +37,40 @@ pub enum Background {
 pub struct Window {
     pub width: usize,
     pub height: usize,
-    pub sprites: Vec<Sprite>,
+    pub sprites: SpriteStore,
     pub background: Option<Background>,
     pub texts: HashMap<String, TextItem>,
-    window: minifb::Window,
+
+    /// Fonts available to text items, by name.
+    pub fonts: HashMap<String, Font>,
+    backend: Box<dyn Backend>,
+
+    frame: FrameBuffer,
 
     pub controls: Input,
 
+    /// Where `controls` is fed from.
+    input: InputSource,
+
+    /// Gamepad devices, once enabled.
+    gamepads: Option<NativeGamepads>,
+
+    /// Events applied ahead of device input on the next frame, from
+    /// `inject` and `start_recording`.
+    pending: Vec<InputEvent>,
+
+    /// Frames captured since `start_recording`.
+    recording: Option<InputRecording>,
+
+    /// Remaining frames of a recording being played back.
+    replay: Option<std::vec::IntoIter<RecordedFrame>>,
+
+    /// Viewport applied to world-space layers.
+    pub camera: Camera,
+
+    /// Frame timing, ticked at the end of every draw.
+    pub clock: Clock,
+
     pub paused: bool,
 }
 
@@ -46,235 +89,408 @@ impl Window {
     /// # Panics
     /// Panics if the window cannot be created.
     pub fn new(title: &str, width: usize, height: usize) -> Self {
-        let mut window = MfWindow::new(
-            title,
-            width,
-            height,
-            WindowOptions {
-                resize: true,
-                ..WindowOptions::default()
-            },
-        )
-        .expect("Failed to create window");
+        Self::with_backend(width, height, MinifbBackend::new(title, width, height))
+    }
 
-        window.set_target_fps(60);
+    /// Creates a new window that reads input from the given source.
+    ///
+    /// [`Window::new`] uses [`InputBackend::Window`]; pass
+    /// [`InputBackend::Native`] to read raw OS devices instead.
+    ///
+    /// # Parameters
+    /// - `title`: Title displayed in the window title bar.
+    /// - `width`: Initial window width in pixels.
+    /// - `height`: Initial window height in pixels.
+    /// - `input`: Source of keyboard, mouse and scroll input.
+    ///
+    /// # Panics
+    /// Panics if the window cannot be created.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use carose::Window;
+    /// use carose::controls::InputBackend;
+    ///
+    /// let mut window = Window::with_input("Game", 640, 480, InputBackend::Native);
+    /// ```
+    pub fn with_input(title: &str, width: usize, height: usize, input: InputBackend) -> Self {
+        let mut window = Self::new(title, width, height);
+        window.input = InputSource::new(input);
+        window
+    }
+
+    /// Creates a window that renders into memory instead of an OS window.
+    ///
+    /// No display is required, which makes this suitable for CI and tests.
+    /// The window behaves exactly like one created with [`Window::new`];
+    /// the last composed frame can be read back with [`Window::frame`].
+    /// Its [`Clock`] runs at a fixed 1/60 s per frame so that runs are
+    /// deterministic.
+    pub fn headless(width: usize, height: usize) -> Self {
+        Self::headless_with(width, height, HeadlessBackend::new(width, height))
+    }
+
+    /// Creates a headless window that closes after `frames` frames.
+    ///
+    /// This is [`Window::headless`] with a
+    /// [`HeadlessBackend::frame_limit`], so `while window.is_open()` loops
+    /// end on their own.
+    ///
+    /// # Example
+    /// ```
+    /// use carose::Window;
+    ///
+    /// let mut window = Window::headless_frames(64, 64, 3);
+    /// let mut frames = 0;
+    /// while window.is_open() {
+    ///     window.update_controls();
+    ///     window.draw();
+    ///     frames += 1;
+    /// }
+    /// assert_eq!(frames, 3);
+    /// ```
+    pub fn headless_frames(width: usize, height: usize, frames: usize) -> Self {
+        Self::headless_with(width, height, HeadlessBackend::new(width, height).frame_limit(frames))
+    }
+
+    fn headless_with(width: usize, height: usize, backend: HeadlessBackend) -> Self {
+        let mut window = Self::with_backend(width, height, backend);
+        window.clock = Clock::fixed(Duration::from_secs(1) / 60);
+        window
+    }
 
+    /// Creates a window that presents its frames through a custom backend.
+    ///
+    /// # Parameters
+    /// - `width`: Width of the render buffer in pixels.
+    /// - `height`: Height of the render buffer in pixels.
+    /// - `backend`: Target that receives every composed frame.
+    pub fn with_backend(width: usize, height: usize, backend: impl Backend + 'static) -> Self {
         Self {
             width,
             height,
-            sprites: Vec::new(),
+            sprites: SpriteStore::new(),
             background: None,
             texts: HashMap::new(),
-            window,
+            fonts: HashMap::new(),
+            backend: Box::new(backend),
+
+            frame: FrameBuffer::new(),
 
             controls: Input::new(),
+            input: InputSource::Window,
+            gamepads: None,
+            pending: Vec::new(),
+            recording: None,
+            replay: None,
+
+            camera: Camera::new(),
+
+            clock: Clock::new(),
 
             paused: false,
         }
     }
 
+    /// Returns the presentation backend of this window.
+    pub fn backend(&self) -> &dyn Backend {
+        self.backend.as_ref()
+    }
+
+    /// Returns the presentation backend of this window mutably.
+    pub fn backend_mut(&mut self) -> &mut dyn Backend {
+        self.backend.as_mut()
+    }
+
     /// Polls and updates input state for the current frame.
     ///
     /// Input is only processed while the window is focused.
     /// This should typically be called once per frame before
-    /// reading input state.
+    /// reading input state. Characters typed since the last call are
+    /// available from [`Input::typed_chars`].
     pub fn update_controls(&mut self) {
-        let focused = self.window.is_active();
-        self.controls.poll(focused);
+        let mut focused = self.backend.is_active();
+        let mut events = Vec::new();
+        self.backend.poll_events(&mut events);
+
+        match &mut self.input {
+            InputSource::Window => {}
+            InputSource::Native(native) => {
+                // Raw devices know nothing about keyboard layouts or where
+                // the window is, so typed characters and the cursor still
+                // come from the window.
+                events.retain(|event| matches!(event, InputEvent::Char(_) | InputEvent::CursorMoved(..)));
+                native.poll(focused, &mut events);
+            }
+            InputSource::Scripted => {
+                events.clear();
+                focused = true;
+            }
+        }
+
+        if let Some(gamepads) = &mut self.gamepads {
+            gamepads.poll(focused, &mut events);
+            if matches!(self.input, InputSource::Scripted) {
+                events.clear();
+            }
+        }
+        events.splice(0..0, self.pending.drain(..));
+
+        // Live devices are still drained during playback so their state
+        // does not leak in once the recording ends.
+        let (focused, events) = match self.replay.as_mut().map(Iterator::next) {
+            Some(Some(frame)) => {
+                self.clock.replace_delta(frame.delta);
+                (frame.focused, frame.events)
+            }
+            Some(None) => {
+                self.replay = None;
+                (focused, events)
+            }
+            None => (focused, events),
+        };
+
+        self.controls.begin_frame(focused, self.clock.delta());
+        if focused {
+            for &event in &events {
+                self.controls.handle_event(event);
+            }
+        }
+
+        if let Some(recording) = &mut self.recording {
+            recording.push(RecordedFrame {
+                delta: self.clock.delta(),
+                focused,
+                events: if focused { events } else { Vec::new() },
+            });
+        }
+    }
+
+    /// Queues an input event for the next [`Self::update_controls`].
+    ///
+    /// Injected events are applied before any device input of that frame,
+    /// in the order they were queued, and are recorded like device input.
+    /// They are ignored while a recording is being played back. Combine
+    /// with [`InputBackend::Scripted`] or [`Window::headless`] so no real
+    /// device interferes, or use [`crate::testing::InputScript`] to lay out
+    /// input frame by frame.
+    ///
+    /// # Example
+    /// ```
+    /// use carose::Window;
+    /// use carose::controls::{InputEvent, Key};
+    ///
+    /// let mut window = Window::headless(320, 240);
+    /// window.inject(InputEvent::KeyDown(Key::Enter));
+    /// window.update_controls();
+    /// assert!(window.controls.just_pressed(Key::Enter));
+    ///
+    /// window.update_controls();
+    /// assert!(window.controls.pressed(Key::Enter));
+    /// assert!(!window.controls.just_pressed(Key::Enter));
+    /// ```
+    pub fn inject(&mut self, event: InputEvent) {
+        self.pending.push(event);
+    }
+
+    /// Starts reading gamepads and returns how many were found.
+    ///
+    /// Every gamepad connected at this point gets a slot in
+    /// [`Input::gamepad`], in device order. Pads connected later are not
+    /// picked up until this is called again, which reassigns all slots.
+    ///
+    /// # Notes
+    /// - Gamepads are read through evdev, so they are only available on
+    ///   Linux. Most desktops grant the logged-in user access to gamepad
+    ///   devices without extra permissions.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use carose::Window;
+    /// use carose::controls::{GamepadAxis, GamepadButton};
+    ///
+    /// let mut window = Window::new("Pads", 640, 480);
+    /// window.enable_gamepads();
+    ///
+    /// while window.is_open() {
+    ///     window.update_controls();
+    ///     if let Some(pad) = window.controls.gamepad_mut(0) {
+    ///         let speed = pad.axis(GamepadAxis::LeftX) * 4.0;
+    ///         let jump = pad.clicked(GamepadButton::South);
+    ///     }
+    ///     window.draw();
+    /// }
+    /// ```
+    pub fn enable_gamepads(&mut self) -> usize {
+        let gamepads = NativeGamepads::new();
+        let count = gamepads.len();
+        self.gamepads = Some(gamepads);
+        count
     }
 
     /// Returns whether the window is currently open.
     ///
     /// This should be used as the main loop condition.
     pub fn is_open(&self) -> bool {
-        self.window.is_open()
+        self.backend.is_open()
     }
     /// Sets the target frames per second for the window.
     ///
     /// This controls how often the window redraws and how input
     /// events are processed.
     pub fn set_target_fps(&mut self, fps: usize) {
-        self.window.set_target_fps(fps)
+        self.backend.set_target_fps(fps)
     }
 
     /// Updates the window title.
     pub fn set_title(&mut self, title: &str) {
-        self.window.set_title(title);
+        self.backend.set_title(title);
     }
 
     /// Sets the window's position on the screen.
     ///
     /// Coordinates are in screen space.
     pub fn set_position(&mut self, x: isize, y: isize) {
-        self.window.set_position(x, y);
+        self.backend.set_position(x, y);
     }
 
     /// Returns the current window position in screen coordinates.
     pub fn get_position(&self) -> (isize, isize) {
-        self.window.get_position()
+        self.backend.get_position()
     }
 
     /// Forces the window to stay above all other windows when enabled.
     pub fn set_topmost_always(&mut self, topmost: bool) {
-        self.window.topmost(topmost);
+        self.backend.topmost(topmost);
     }
 
     /// Shows or hides the mouse cursor while the window is focused.
     pub fn set_cursor_visibility(&mut self, visible: bool) {
-        self.window.set_cursor_visibility(visible);
+        self.backend.set_cursor_visibility(visible);
     }
 
     /// Returns the current window size.
     pub fn get_size(&self) -> (usize, usize) {
-        self.window.get_size()
+        self.backend.get_size()
     }
 
     /// Returns the current window width in pixels.
     pub fn get_width(&self) -> usize {
-        self.window.get_size().0
+        self.backend.get_size().0
     }
 
     /// Returns the current window height in pixels.
     pub fn get_height(&self) -> usize {
-        self.window.get_size().1
+        self.backend.get_size().1
     }
 
     /// Returns whether the window currently has input focus.
     pub fn is_focused(&mut self) -> bool {
-        self.window.is_active()
+        self.backend.is_active()
     }
     
     /// Renders a complete frame.
     ///
     /// This method:
     /// - Clears the screen using the configured background
-    /// - Draws all sprites (including animated sprites)
-    /// - Draws all text using a built-in 5x5 bitmap font
+    /// - Draws all sprites (including animated sprites) and text using a
+    ///   built-in 5x5 bitmap font, ordered by [`Layer`] and z-index
+    /// - Moves the [`Camera`] and applies it to world-space layers
     /// - Advances sprite animations
-    /// - Uploads the final frame buffer to the window
+    /// - Presents the final frame buffer through the window's backend
+    /// - Ticks the window's [`Clock`]
+    ///
+    /// The composed frame is kept afterwards and can be read back with
+    /// [`Window::frame`] or saved with [`Window::save_frame`].
     ///
     /// This should be called once per frame.
     pub fn draw(&mut self) {
-        // --- Create 2D buffer with background ---
-        let mut buffer: Vec<Vec<u32>> = match &self.background {
-            Some(Background::Color(color)) => {
-                vec![vec![*color; self.width]; self.height]
-            }
-            Some(Background::Image(image)) => image.clone(),
-            None => vec![vec![0x000000; self.width]; self.height],
-        };
+        self.camera.update(&self.sprites, self.width, self.height);
 
-        // --- Draw sprites ---
-        for sprite in &mut self.sprites {
-            let (sx, sy) = sprite.position;
-
-            match &mut sprite.render {
-                SpriteRender::Color(color) => {
-                    let (w, h) = sprite.size;
-                    for y in 0..h {
-                        for x in 0..w {
-                            let px = sx + x;
-                            let py = sy + y;
-                            if px < self.width && py < self.height {
-                                buffer[py][px] = *color;
-                            }
-                        }
-                    }
-                }
-
-                SpriteRender::Bitmap { pixels } => {
-                    let h = pixels.len();
-                    if h == 0 { continue; }
-                    let w = pixels[0].len();
-
-                    for y in 0..h {
-                        for x in 0..w {
-                            let pixel = pixels[y][x];
-                            if pixel == 0 { continue; }
-
-                            let px = sx + x;
-                            let py = sy + y;
-                            if px < self.width && py < self.height {
-                                buffer[py][px] = pixel;
-                            }
-                        }
-                    }
-                }
-
-                SpriteRender::AnimatedBitmap {
-                    frames,
-                    frame_index,
-                    frame_delay,
-                    frame_timer,
-                } => {
-                    if frames.is_empty() { continue; }
-
-                    let frame = &frames[*frame_index];
-                    let h = frame.len();
-                    if h == 0 { continue; }
-                    let w = frame[0].len();
-
-                    for y in 0..h {
-                        for x in 0..w {
-                            let pixel = frame[y][x];
-                            if pixel == 0 { continue; }
-
-                            let px = sx + x;
-                            let py = sy + y;
-                            if px < self.width && py < self.height {
-                                buffer[py][px] = pixel;
-                            }
-                        }
-                    }
-
-                    // advance animation
-                    *frame_timer += 1;
-                    if *frame_timer >= *frame_delay {
-                        *frame_timer = 0;
-                        *frame_index = (*frame_index + 1) % frames.len();
-                    }
-                }
-            }
+        let frame = &mut self.frame;
+        frame.resize(self.width, self.height);
+
+        // --- Clear with background ---
+        match &self.background {
+            Some(Background::Color(color)) => frame.clear(*color),
+            Some(Background::Image(image)) => frame.copy_image(image),
+            None => frame.clear(0x000000),
         }
 
-        // --- Draw texts using 5x5 bitmap font ---
-        let font_map = get_font_map();
-
-        for text_item in self.texts.values() {
-            let (tx, ty) = text_item.position;
-            let color = text_item.color;
-            let size = text_item.size;
-
-            for (i, c) in text_item.content.chars().enumerate() {
-                let char_pixels = font_map
-                    .get(&c.to_ascii_uppercase())
-                    .unwrap_or(&font_map[&' ']);
-
-                for y in 0..5 {
-                    for x in 0..5 {
-                        if char_pixels[y][x] != 0 {
-                            for sy in 0..size {
-                                for sx in 0..size {
-                                    let px = tx + i * (5 * size + 7) + x * size + sx;
-                                    let py = ty + y * size + sy;
-                                    if px < self.width && py < self.height {
-                                        buffer[py][px] = color;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        // --- Sort by layer; ties keep sprites in insertion order, then texts by id ---
+        let mut texts: Vec<(&String, &TextItem)> = self.texts.iter().collect();
+        texts.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut items: Vec<DrawItem> = Vec::with_capacity(self.sprites.len() + texts.len());
+        items.extend(self.sprites.values().iter().enumerate().map(|(i, sprite)| {
+            DrawItem { layer: sprite.layer, z_index: sprite.z_index, kind: DrawKind::Sprite(i) }
+        }));
+        items.extend(texts.iter().enumerate().map(|(i, (_, text))| {
+            DrawItem { layer: text.layer, z_index: text.z_index, kind: DrawKind::Text(i) }
+        }));
+        items.sort_by_key(|item| (item.layer, item.z_index));
+
+        // --- Draw in layer order ---
+        let sprites = self.sprites.values_mut();
+        for item in &items {
+            let view = self.camera.view_for(item.layer);
+            match item.kind {
+                DrawKind::Sprite(i) => draw_sprite(frame, &mut sprites[i], view),
+                DrawKind::Text(i) => text::draw_text(frame, texts[i].1, &self.fonts, view),
             }
         }
 
-        // --- Flatten 2D buffer into 1D ---
-        let flat_buffer: Vec<u32> = buffer.into_iter().flatten().collect();
-
-        self.window
-            .update_with_buffer(&flat_buffer, self.width, self.height)
-            .unwrap();
+        let (width, height) = frame.size();
+        self.backend.present(frame.pixels(), width, height);
+        self.clock.tick();
     }
 
 }
 
+/// Entry in the per-frame draw list.
+struct DrawItem {
+    layer: Layer,
+    z_index: i32,
+    kind: DrawKind,
+}
+
+/// What a [`DrawItem`] refers to, by position in the frame's sprite or text list.
+enum DrawKind {
+    Sprite(usize),
+    Text(usize),
+}
+
+/// Draws one sprite through a view and advances its animation.
+fn draw_sprite(frame: &mut FrameBuffer, sprite: &mut Sprite, view: View) {
+    let (sx, sy) = view.apply(sprite.position);
+    let zoom = view.zoom;
+
+    match &mut sprite.render {
+        SpriteRender::Color(color) => {
+            let (w, h) = sprite.size;
+            frame.blend_rect(sx, sy, w * zoom, h * zoom, *color, sprite.opacity);
+        }
+
+        SpriteRender::Bitmap { pixels } => {
+            frame.blit(sx, sy, pixels, zoom, sprite.opacity);
+        }
+
+        SpriteRender::AnimatedBitmap {
+            frames,
+            frame_index,
+            frame_delay,
+            frame_timer,
+        } => {
+            if frames.is_empty() { return; }
+
+            frame.blit(sx, sy, &frames[*frame_index], zoom, sprite.opacity);
+
+            // advance animation
+            *frame_timer += 1;
+            if *frame_timer >= *frame_delay {
+                *frame_timer = 0;
+                *frame_index = (*frame_index + 1) % frames.len();
+            }
+        }
+    }
+}
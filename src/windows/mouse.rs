@@ -0,0 +1,171 @@
+use crate::controls::Key;
+use crate::sprites::{Sprite, SpriteId};
+
+
+impl super::Window {
+    /// Returns the cursor position in frame pixels.
+    ///
+    /// The position reported by the input source is relative to the
+    /// presentation target, which may be stretched to a different size
+    /// than the frame; this maps it back onto the frame. The result can lie
+    /// outside the frame while the cursor is outside the window.
+    pub fn mouse_position(&self) -> (i32, i32) {
+        let (x, y) = self.controls.cursor_position();
+        let (target_width, target_height) = self.backend.get_size();
+        if target_width == 0 || target_height == 0 {
+            return (x, y);
+        }
+
+        (
+            (x as i64 * self.width as i64 / target_width as i64) as i32,
+            (y as i64 * self.height as i64 / target_height as i64) as i32,
+        )
+    }
+
+    /// Returns the cursor position in world coordinates.
+    ///
+    /// This applies the inverse of the [`super::Camera`] transform, so the
+    /// result can be compared directly with sprite positions on world-space
+    /// layers.
+    pub fn mouse_world_position(&self) -> (f32, f32) {
+        let (x, y) = self.mouse_position();
+        self.camera.screen_to_world((x as f32, y as f32))
+    }
+
+    /// Returns the topmost sprite drawn at a frame pixel.
+    ///
+    /// Sprites are tested in reverse draw order, so the one visible on top
+    /// wins. World-space layers are tested through the camera, and bitmap
+    /// sprites only match over pixels that are not fully transparent.
+    ///
+    /// # Parameters
+    /// - `point`: Position in frame pixels, e.g. from [`Self::mouse_position`].
+    ///
+    /// # Notes
+    /// - Text is ignored and never hides the sprites below it.
+    /// - Sprites have no draw-time scale: [`Sprite::upscale`] rewrites the
+    ///   sprite's own size and pixels, so picking matches what is drawn.
+    ///   Only the camera zoom scales sprites, and it is applied here too.
+    pub fn sprite_at(&self, point: (i32, i32)) -> Option<SpriteId> {
+        let screen = (point.0 as f32 + 0.5, point.1 as f32 + 0.5);
+
+        let mut order: Vec<(SpriteId, &Sprite)> = self.sprites.iter().collect();
+        order.sort_by_key(|(_, sprite)| (sprite.layer, sprite.z_index));
+
+        order.into_iter().rev().find_map(|(id, sprite)| {
+            let local = self.camera.view_for(sprite.layer).invert(screen);
+            sprite.contains_point(local).then_some(id)
+        })
+    }
+
+    /// Returns the topmost sprite under the cursor.
+    pub fn hovered_sprite(&self) -> Option<SpriteId> {
+        self.sprite_at(self.mouse_position())
+    }
+
+    /// Returns the topmost sprite under the cursor when `button` is clicked.
+    ///
+    /// Like [`crate::controls::Input::clicked`], this consumes the click,
+    /// so it reports a given click only once.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use carose::Window;
+    /// use carose::controls::Key;
+    ///
+    /// let mut window = Window::new("Picking", 640, 480);
+    /// while window.is_open() {
+    ///     window.update_controls();
+    ///     if let Some(id) = window.clicked_sprite(Key::MouseLeft) {
+    ///         window.remove_sprite(id);
+    ///     }
+    ///     window.draw();
+    /// }
+    /// ```
+    pub fn clicked_sprite(&mut self, button: Key) -> Option<SpriteId> {
+        if self.controls.clicked(button) {
+            self.hovered_sprite()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Layer;
+    use crate::controls::InputEvent;
+    use crate::sprites::SpriteType;
+    use crate::windows::backend::HeadlessBackend;
+    use crate::Window;
+
+    fn block(position: (f32, f32), size: usize, layer: Layer, z_index: i32) -> Sprite {
+        let mut sprite = Sprite::new_color(position, (size, size), SpriteType::Wall, 1, 0xFFFFFF, false);
+        sprite.layer = layer;
+        sprite.z_index = z_index;
+        sprite
+    }
+
+    #[test]
+    fn topmost_layer_then_z_index_wins() {
+        let mut window = Window::headless(40, 40);
+        let hud = window.add_sprite(block((0.0, 0.0), 10, Layer::HUD, -5));
+        let high = window.add_sprite(block((5.0, 5.0), 10, Layer::WORLD, 3));
+        let low = window.add_sprite(block((5.0, 5.0), 20, Layer::WORLD, 1));
+
+        assert_eq!(window.sprite_at((7, 7)), Some(hud));
+        assert_eq!(window.sprite_at((12, 12)), Some(high));
+        assert_eq!(window.sprite_at((20, 20)), Some(low));
+        assert_eq!(window.sprite_at((30, 30)), None);
+    }
+
+    #[test]
+    fn transparent_pixels_fall_through() {
+        let mut window = Window::headless(40, 40);
+        let below = window.add_sprite(block((0.0, 0.0), 4, Layer::WORLD, 0));
+        let pixels = vec![vec![0x00FF0000, 0xFFFF0000], vec![0xFFFF0000, 0xFFFF0000]];
+        let mut above = Sprite::new_bitmap((0.0, 0.0), SpriteType::Wall, 1, pixels, false);
+        above.z_index = 1;
+        above.upscale(2);
+        let above = window.add_sprite(above);
+
+        assert_eq!(window.sprite_at((1, 1)), Some(below));
+        assert_eq!(window.sprite_at((2, 1)), Some(above));
+        assert_eq!(window.sprite_at((3, 3)), Some(above));
+    }
+
+    #[test]
+    fn world_layers_are_picked_through_the_camera() {
+        let mut window = Window::headless(40, 40);
+        window.camera.position = (100.0, 50.0);
+        window.camera.zoom = 2;
+        let world = window.add_sprite(block((105.0, 55.0), 5, Layer::WORLD, 0));
+        let hud = window.add_sprite(block((0.0, 0.0), 5, Layer::HUD, 0));
+
+        // World (105, 55) is screen (10, 10); the zoomed sprite covers 10 pixels.
+        assert_eq!(window.sprite_at((9, 9)), None);
+        assert_eq!(window.sprite_at((10, 10)), Some(world));
+        assert_eq!(window.sprite_at((19, 19)), Some(world));
+        assert_eq!(window.sprite_at((20, 20)), None);
+
+        // The HUD stays put in screen space.
+        assert_eq!(window.sprite_at((4, 4)), Some(hud));
+        assert_eq!(window.sprite_at((5, 5)), None);
+    }
+
+    #[test]
+    fn mouse_position_maps_the_target_onto_the_frame() {
+        let mut window = Window::with_backend(100, 50, HeadlessBackend::new(200, 200));
+        window.inject(InputEvent::CursorMoved(100, 100));
+        window.update_controls();
+        assert_eq!(window.mouse_position(), (50, 25));
+
+        window.inject(InputEvent::CursorMoved(-20, 300));
+        window.update_controls();
+        assert_eq!(window.mouse_position(), (-10, 75));
+
+        window.camera.position = (10.0, 20.0);
+        assert_eq!(window.mouse_world_position(), (0.0, 95.0));
+    }
+}
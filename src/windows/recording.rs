@@ -0,0 +1,107 @@
+use crate::controls::InputRecording;
+
+
+impl super::Window {
+    /// Starts recording the input of every following frame.
+    ///
+    /// Whatever is held when recording starts is released and pressed
+    /// again on the first recorded frame, so the live session and its
+    /// playback begin from the same input state. Events queued with
+    /// [`Self::inject`] are kept and applied after that state, on the same
+    /// frame. Any recording already in progress is discarded.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use carose::Window;
+    /// use carose::controls::Key;
+    ///
+    /// let mut window = Window::new("Recorder", 640, 480);
+    /// window.start_recording();
+    ///
+    /// while window.is_open() && !window.controls.just_pressed(Key::Escape) {
+    ///     window.update_controls();
+    ///     window.draw();
+    /// }
+    ///
+    /// if let Some(recording) = window.stop_recording() {
+    ///     recording.save("session.rec").unwrap();
+    /// }
+    /// ```
+    pub fn start_recording(&mut self) {
+        self.pending.splice(0..0, self.controls.state_events());
+        self.controls.reset();
+        self.recording = Some(InputRecording::new());
+    }
+
+    /// Stops recording and returns the frames recorded so far, or `None`
+    /// if nothing was being recorded.
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recording.take()
+    }
+
+    /// Returns `true` while input is being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Plays a recording back in place of live input.
+    ///
+    /// Every following call to [`Self::update_controls`] consumes one
+    /// recorded frame: its events are applied instead of device input, and
+    /// its duration replaces the clock's delta time. Live input resumes
+    /// once the recording runs out.
+    ///
+    /// # Notes
+    /// - All keys and buttons are released when playback starts.
+    /// - Playback is exact as long as the game only depends on input, the
+    ///   clock's delta time and an RNG seeded the same way as when
+    ///   recording.
+    /// - Playing back while recording records the played frames.
+    pub fn play_recording(&mut self, recording: InputRecording) {
+        self.controls.reset();
+        self.pending.clear();
+        self.replay = Some(recording.into_iter());
+    }
+
+    /// Returns `true` while recorded frames remain to be played back.
+    ///
+    /// A loop that calls [`Self::update_controls`] while this holds
+    /// processes every recorded frame exactly once.
+    pub fn is_replaying(&self) -> bool {
+        self.replay.as_ref().is_some_and(|frames| frames.len() > 0)
+    }
+
+    /// Stops playback and returns to live input.
+    pub fn stop_replay(&mut self) {
+        self.replay = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Window;
+    use crate::controls::{InputEvent, Key};
+
+    #[test]
+    fn injected_events_survive_start_recording() {
+        let mut window = Window::headless(8, 8);
+        window.inject(InputEvent::KeyDown(Key::Space));
+        window.update_controls();
+
+        window.inject(InputEvent::KeyDown(Key::Enter));
+        window.inject(InputEvent::KeyUp(Key::Space));
+        window.start_recording();
+        window.update_controls();
+
+        assert!(window.controls.just_pressed(Key::Enter));
+        assert!(window.controls.released(Key::Space));
+        let recording = window.stop_recording().unwrap();
+        let keys: Vec<_> = recording.frames()[0]
+            .events
+            .iter()
+            .filter(|event| matches!(event, InputEvent::KeyDown(_) | InputEvent::KeyUp(_)))
+            .copied()
+            .collect();
+        assert_eq!(keys, [InputEvent::KeyDown(Key::Space), InputEvent::KeyDown(Key::Enter), InputEvent::KeyUp(Key::Space)]);
+    }
+}
@@ -0,0 +1,313 @@
+/// Visible part of a rectangle after clipping against the frame buffer.
+///
+/// `x0..x1` and `y0..y1` are buffer coordinates; `(ox, oy)` is how far the
+/// visible part starts from the unclipped origin.
+struct Clip {
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+    ox: usize,
+    oy: usize,
+}
+
+/// Bits of a pixel that hold its color; the frame buffer stores pixels
+/// as `0xRRGGBB` with the top byte always zero.
+const RGB_MASK: u32 = 0x00FF_FFFF;
+
+/// Persistent flat frame buffer the window composes every frame into.
+///
+/// Pixels are stored row-major as `0xRRGGBB` in a single allocation that
+/// is reused across frames. Any alpha byte of an input color is dropped,
+/// so equal colors always compare equal. Row `y` starts at `y * stride`.
+/// All drawing operations take signed coordinates and clip against the
+/// buffer bounds, so callers never need to pre-check whether something
+/// is on screen.
+///
+/// An optional clip rectangle further restricts where drawing operations
+/// may write, e.g. to keep text inside its box.
+pub(crate) struct FrameBuffer {
+    pixels: Vec<u32>,
+    width: usize,
+    height: usize,
+    stride: usize,
+    clip_rect: Option<(i64, i64, i64, i64)>,
+}
+
+impl FrameBuffer {
+    /// Creates an empty buffer. Call [`FrameBuffer::resize`] before drawing.
+    pub(crate) fn new() -> Self {
+        Self {
+            pixels: Vec::new(),
+            width: 0,
+            height: 0,
+            stride: 0,
+            clip_rect: None,
+        }
+    }
+
+    /// Resizes the buffer, reallocating only when the dimensions change.
+    pub(crate) fn resize(&mut self, width: usize, height: usize) {
+        if self.width == width && self.height == height {
+            return;
+        }
+        self.width = width;
+        self.height = height;
+        self.stride = width;
+        self.pixels.resize(width * height, 0);
+    }
+
+    /// Returns the composed pixels in row-major order.
+    pub(crate) fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    /// Returns the `(width, height)` of the buffer.
+    pub(crate) fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns row `y` as a mutable slice.
+    fn row_mut(&mut self, y: usize) -> &mut [u32] {
+        let start = y * self.stride;
+        &mut self.pixels[start..start + self.width]
+    }
+
+    /// Restricts drawing to a rectangle `(x, y, w, h)`, or lifts the
+    /// restriction with `None`.
+    ///
+    /// Only [`FrameBuffer::clear`] and [`FrameBuffer::copy_image`] ignore
+    /// the clip rectangle.
+    pub(crate) fn set_clip(&mut self, rect: Option<(i32, i32, usize, usize)>) {
+        self.clip_rect = rect.map(|(x, y, w, h)| {
+            (x as i64, y as i64, x as i64 + w as i64, y as i64 + h as i64)
+        });
+    }
+
+    /// Fills the whole buffer with a single color.
+    pub(crate) fn clear(&mut self, color: u32) {
+        self.pixels.fill(color & RGB_MASK);
+    }
+
+    /// Copies a full-screen image into the buffer, row by row.
+    ///
+    /// Rows or columns that exceed the buffer are ignored; any area the
+    /// image does not cover is filled with black.
+    pub(crate) fn copy_image(&mut self, image: &[Vec<u32>]) {
+        if image.len() < self.height || image.iter().any(|row| row.len() < self.width) {
+            self.clear(0x000000);
+        }
+
+        for (y, src) in image.iter().take(self.height).enumerate() {
+            let len = src.len().min(self.width);
+            for (d, &s) in self.row_mut(y)[..len].iter_mut().zip(src) {
+                *d = s & RGB_MASK;
+            }
+        }
+    }
+
+    /// Clips a rectangle against the buffer and the clip rectangle.
+    ///
+    /// Returns `None` if nothing is visible.
+    fn clip(&self, x: i32, y: i32, w: usize, h: usize) -> Option<Clip> {
+        let (cx0, cy0, cx1, cy1) = self.clip_rect.unwrap_or((0, 0, self.width as i64, self.height as i64));
+        let x0 = (x as i64).max(cx0).max(0);
+        let y0 = (y as i64).max(cy0).max(0);
+        let x1 = (x as i64 + w as i64).min(cx1).min(self.width as i64);
+        let y1 = (y as i64 + h as i64).min(cy1).min(self.height as i64);
+
+        if x0 >= x1 || y0 >= y1 {
+            return None;
+        }
+
+        Some(Clip {
+            x0: x0 as usize,
+            y0: y0 as usize,
+            x1: x1 as usize,
+            y1: y1 as usize,
+            ox: (x0 - x as i64) as usize,
+            oy: (y0 - y as i64) as usize,
+        })
+    }
+
+    /// Fills a rectangle with a solid color.
+    pub(crate) fn fill_rect(&mut self, x: i32, y: i32, w: usize, h: usize, color: u32) {
+        let Some(Clip { x0, y0, x1, y1, .. }) = self.clip(x, y, w, h) else { return };
+
+        for py in y0..y1 {
+            self.row_mut(py)[x0..x1].fill(color & RGB_MASK);
+        }
+    }
+
+    /// Fills a rectangle with a color blended at the given opacity.
+    ///
+    /// The alpha byte of `color` is ignored; `opacity` alone controls
+    /// coverage. An opacity of `255` is equivalent to [`FrameBuffer::fill_rect`].
+    pub(crate) fn blend_rect(&mut self, x: i32, y: i32, w: usize, h: usize, color: u32, opacity: u8) {
+        match opacity {
+            0 => {}
+            255 => self.fill_rect(x, y, w, h, color),
+            _ => {
+                let Some(Clip { x0, y0, x1, y1, .. }) = self.clip(x, y, w, h) else { return };
+
+                for py in y0..y1 {
+                    for d in &mut self.row_mut(py)[x0..x1] {
+                        *d = blend(*d, color, opacity as u32);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws an ARGB bitmap using source-over alpha blending.
+    ///
+    /// Each source pixel covers a `scale` x `scale` block. Each pixel's
+    /// alpha is multiplied by `opacity`, so `255` draws the bitmap as-is
+    /// and `0` draws nothing. Pixels with zero alpha are skipped.
+    pub(crate) fn blit(&mut self, x: i32, y: i32, src: &[Vec<u32>], scale: usize, opacity: u8) {
+        if opacity == 0 || scale == 0 {
+            return;
+        }
+
+        let h = src.len();
+        let w = src.first().map_or(0, |row| row.len());
+        let Some(Clip { x0, y0, x1, y1, ox, oy }) = self.clip(x, y, w * scale, h * scale) else { return };
+        let opacity = opacity as u32;
+
+        for py in y0..y1 {
+            let src_row = &src[(oy + py - y0) / scale];
+            let dst = &mut self.row_mut(py)[x0..x1];
+
+            if scale == 1 {
+                let src_row = &src_row[ox.min(src_row.len())..];
+                for (d, &s) in dst.iter_mut().zip(src_row) {
+                    blend_pixel(d, s, opacity);
+                }
+            } else {
+                for (i, d) in dst.iter_mut().enumerate() {
+                    let Some(&s) = src_row.get((ox + i) / scale) else { break };
+                    blend_pixel(d, s, opacity);
+                }
+            }
+        }
+    }
+}
+
+/// Draws one ARGB source pixel over `dst`, scaled by `opacity` (`0..=255`).
+#[inline]
+fn blend_pixel(dst: &mut u32, src: u32, opacity: u32) {
+    let alpha = (src >> 24) * opacity / 255;
+    match alpha {
+        0 => {}
+        255 => *dst = src & RGB_MASK,
+        _ => *dst = blend(*dst, src, alpha),
+    }
+}
+
+/// Blends `src` over `dst` with the given alpha (`0..=255`).
+///
+/// Only the RGB channels of both colors are used; the result has no
+/// alpha byte.
+fn blend(dst: u32, src: u32, alpha: u32) -> u32 {
+    let inv = 255 - alpha;
+    let channel = |shift: u32| {
+        let s = (src >> shift) & 0xFF;
+        let d = (dst >> shift) & 0xFF;
+        ((s * alpha + d * inv + 127) / 255) << shift
+    };
+
+    channel(16) | channel(8) | channel(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprites::{Sprite, SpriteType};
+
+    #[test]
+    fn blended_and_copied_pixels_have_no_alpha_byte() {
+        let mut buffer = FrameBuffer::new();
+        buffer.resize(4, 1);
+        buffer.clear(0xFF00_0000);
+        buffer.fill_rect(0, 0, 1, 1, 0xFF12_3456);
+        buffer.blend_rect(1, 0, 1, 1, 0xFFFF_FFFF, 128);
+        buffer.blit(2, 0, &[vec![0xFF12_3456, 0x80FF_FFFF]], 1, 255);
+
+        assert_eq!(buffer.pixels(), &[0x12_3456, 0x80_8080, 0x12_3456, 0x80_8080]);
+    }
+
+    /// A `w` x `h` bitmap whose opaque pixels encode their own position
+    /// as `0xFF00YYXX`, one-based so no pixel is black.
+    fn numbered(w: usize, h: usize) -> Vec<Vec<u32>> {
+        (0..h).map(|y| (0..w).map(|x| 0xFF00_0000 | ((y as u32 + 1) << 8) | (x as u32 + 1)).collect()).collect()
+    }
+
+    fn sized(width: usize, height: usize) -> FrameBuffer {
+        let mut buffer = FrameBuffer::new();
+        buffer.resize(width, height);
+        buffer
+    }
+
+    fn rows(buffer: &FrameBuffer) -> Vec<&[u32]> {
+        buffer.pixels().chunks(buffer.size().0).collect()
+    }
+
+    #[test]
+    fn blit_clips_at_the_left_and_top_edges() {
+        let mut buffer = sized(4, 3);
+        buffer.blit(-1, -2, &numbered(3, 3), 1, 255);
+        assert_eq!(rows(&buffer), [&[0x0302, 0x0303, 0, 0][..], &[0; 4], &[0; 4]]);
+
+        let mut buffer = sized(4, 3);
+        buffer.blit(-3, -1, &numbered(2, 2), 2, 255);
+        assert_eq!(rows(&buffer), [&[0x0102, 0, 0, 0][..], &[0x0202, 0, 0, 0], &[0x0202, 0, 0, 0]]);
+    }
+
+    #[test]
+    fn blit_clips_at_the_right_and_bottom_edges() {
+        let mut buffer = sized(4, 3);
+        buffer.blit(2, 1, &numbered(3, 3), 1, 255);
+        assert_eq!(rows(&buffer), [&[0; 4][..], &[0, 0, 0x0101, 0x0102], &[0, 0, 0x0201, 0x0202]]);
+    }
+
+    #[test]
+    fn rects_clip_at_every_edge() {
+        let mut buffer = sized(4, 3);
+        buffer.fill_rect(-2, -5, 3, 6, 0x11);
+        buffer.blend_rect(3, 2, 10, 10, 0xFF, 255);
+        assert_eq!(rows(&buffer), [&[0x11, 0, 0, 0][..], &[0; 4], &[0, 0, 0, 0xFF]]);
+    }
+
+    #[test]
+    fn fully_offscreen_drawing_changes_nothing() {
+        let mut buffer = sized(4, 3);
+        buffer.clear(0x123456);
+        let bitmap = numbered(3, 3);
+        for (x, y) in [(-3, 0), (0, -3), (4, 0), (0, 3), (-100, -100), (i32::MIN, i32::MIN), (i32::MAX, i32::MAX)] {
+            buffer.fill_rect(x, y, 3, 3, 0xFF);
+            buffer.blend_rect(x, y, 3, 3, 0xFF, 128);
+            buffer.blit(x, y, &bitmap, 1, 255);
+        }
+        // Scaled up, the bitmap covers 12x12 pixels.
+        for (x, y) in [(-12, 0), (0, -12), (4, 0), (0, 3), (i32::MIN, 0), (i32::MAX, 0)] {
+            buffer.blit(x, y, &bitmap, 4, 255);
+        }
+        assert!(buffer.pixels().iter().all(|&p| p == 0x123456));
+    }
+
+    #[test]
+    fn sprites_partly_off_screen_draw_their_visible_part() {
+        let mut window = crate::Window::headless(4, 3);
+        window.add_sprite(Sprite::new_bitmap((-2.0, -1.0), SpriteType::Wall, 1, numbered(3, 3), false));
+        window.draw();
+        let frame: Vec<&[u32]> = window.frame().chunks(4).collect();
+        assert_eq!(frame, [&[0x0203, 0, 0, 0][..], &[0x0303, 0, 0, 0], &[0; 4]]);
+    }
+
+    #[test]
+    fn blend_mixes_channels() {
+        assert_eq!(blend(0x00_0000, 0xFF_FFFF, 255), 0xFF_FFFF);
+        assert_eq!(blend(0xFF_FFFF, 0x00_0000, 0), 0xFF_FFFF);
+        assert_eq!(blend(0x00_00FF, 0xFF_0000, 128), 0x80_007F);
+    }
+}
@@ -0,0 +1,156 @@
+use super::Window;
+
+/// Longest frame time the runner will catch up on, in seconds.
+///
+/// Prevents a long stall (e.g. a breakpoint or window drag) from
+/// triggering hundreds of update steps in a row.
+const MAX_FRAME_TIME: f32 = 0.25;
+
+/// Game logic driven by [`Window::run`].
+pub trait Game {
+    /// Advances the game by exactly `dt` seconds.
+    ///
+    /// Called zero or more times per frame at a fixed timestep.
+    fn update(&mut self, window: &mut Window, dt: f32);
+
+    /// Prepares the frame before it is drawn.
+    ///
+    /// `alpha` (`0.0..1.0`) is how far the current time lies between the
+    /// last update and the next one, for interpolating positions.
+    fn render(&mut self, _window: &mut Window, _alpha: f32) {}
+}
+
+impl Window {
+    /// Runs a fixed-timestep game loop until the window closes.
+    ///
+    /// Each frame polls input, calls [`Game::update`] as many times as
+    /// needed to catch up with real time in steps of
+    /// `1 / updates_per_second` seconds, calls [`Game::render`] with the
+    /// interpolation factor, and draws.
+    ///
+    /// Input edges such as [`crate::controls::Input::just_pressed`], typed
+    /// characters and scroll are held until an update has run and then
+    /// cleared, so every edge reaches exactly one [`Game::update`] however
+    /// many steps a frame runs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use carose::Window;
+    /// use carose::windows::Game;
+    ///
+    /// struct Demo;
+    ///
+    /// impl Game for Demo {
+    ///     fn update(&mut self, window: &mut Window, _dt: f32) {
+    ///         window.apply_vectors();
+    ///     }
+    /// }
+    ///
+    /// let mut window = Window::new("Demo", 800, 600);
+    /// window.run(&mut Demo, 60);
+    /// ```
+    pub fn run<G: Game>(&mut self, game: &mut G, updates_per_second: u32) {
+        let step = 1.0 / updates_per_second.max(1) as f32;
+        let mut accumulator = 0.0;
+        let latch_edges = self.controls.latch_edges;
+        self.controls.latch_edges = true;
+
+        while self.is_open() {
+            self.update_controls();
+
+            accumulator += self.clock.delta_secs().min(MAX_FRAME_TIME);
+            while accumulator >= step {
+                game.update(self, step);
+                self.controls.consume_edges();
+                accumulator -= step;
+            }
+
+            game.render(self, accumulator / step);
+            self.draw();
+        }
+
+        self.controls.latch_edges = latch_edges;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controls::{InputEvent, Key};
+    use crate::windows::{Clock, HeadlessBackend};
+    use std::time::Duration;
+
+    /// Counts updates and how many of them saw Space go down or up.
+    #[derive(Default)]
+    struct Probe {
+        updates: usize,
+        pressed: usize,
+        released: usize,
+        frame: usize,
+        script: Vec<(usize, InputEvent)>,
+    }
+
+    impl Game for Probe {
+        fn update(&mut self, window: &mut Window, _dt: f32) {
+            self.updates += 1;
+            self.pressed += window.controls.just_pressed(Key::Space) as usize;
+            self.released += window.controls.just_released(Key::Space) as usize;
+        }
+
+        fn render(&mut self, window: &mut Window, _alpha: f32) {
+            for &(frame, event) in &self.script {
+                if frame == self.frame {
+                    window.inject(event);
+                }
+            }
+            self.frame += 1;
+        }
+    }
+
+    fn run_frames(frames: usize, updates_per_second: u32, script: Vec<(usize, InputEvent)>) -> Probe {
+        let backend = HeadlessBackend::new(8, 8).frame_limit(frames);
+        let mut window = Window::with_backend(8, 8, backend);
+        window.clock = Clock::fixed(Duration::from_secs(1) / 60);
+
+        let mut probe = Probe { script, ..Probe::default() };
+        window.run(&mut probe, updates_per_second);
+        probe
+    }
+
+    #[test]
+    fn edges_survive_frames_without_a_step() {
+        // A tap within one frame, at half the update rate of the frame rate.
+        let probe = run_frames(
+            8,
+            30,
+            vec![(0, InputEvent::KeyDown(Key::Space)), (0, InputEvent::KeyUp(Key::Space))],
+        );
+        assert!(probe.updates > 0);
+        assert_eq!(probe.pressed, 1);
+        assert_eq!(probe.released, 1);
+    }
+
+    #[test]
+    fn edges_reach_only_the_first_catch_up_step() {
+        let probe = run_frames(
+            4,
+            240,
+            vec![(0, InputEvent::KeyDown(Key::Space)), (1, InputEvent::KeyUp(Key::Space))],
+        );
+        assert!(probe.updates > 4);
+        assert_eq!(probe.pressed, 1);
+        assert_eq!(probe.released, 1);
+    }
+
+    #[test]
+    fn run_restores_per_frame_edges() {
+        let mut window = Window::with_backend(8, 8, HeadlessBackend::new(8, 8).frame_limit(1));
+        window.run(&mut Probe::default(), 60);
+
+        window.inject(InputEvent::KeyDown(Key::Space));
+        window.update_controls();
+        window.update_controls();
+        assert!(!window.controls.just_pressed(Key::Space));
+    }
+}
@@ -1,10 +1,99 @@
 use std::collections::HashMap;
+use super::render::FrameBuffer;
+use super::Layer;
+use super::camera::View;
+use super::font::Font;
+use super::text_box::{self, TextBox};
 
+/// Horizontal alignment of a text item relative to its position.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum TextAlign {
+    /// The position is the left edge of the text.
     Left,
+
+    /// The position is the horizontal center of the text.
     Center,
+
+    /// The position is the right edge of the text.
     Right,
-    AutoFit, // scale text to fit in window width
+
+    /// The position is the left edge of the text, and the size is reduced
+    /// as far as needed for the text to end inside the window.
+    AutoFit,
+}
+
+/// Drop shadow drawn behind a text item.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextShadow {
+    /// Offset of the shadow from the text in pixels.
+    pub offset: (i32, i32),
+
+    /// Shadow color in 0xRRGGBB format.
+    pub color: u32,
+}
+
+/// Rectangle drawn behind a text item.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextBackground {
+    /// Panel color in 0xRRGGBB format.
+    pub color: u32,
+
+    /// Panel opacity, from `0` (invisible) to `255` (opaque).
+    pub opacity: u8,
+
+    /// Space in pixels between the text and the panel edges.
+    pub padding: usize,
+}
+
+/// Decorations that keep text readable over busy backgrounds.
+///
+/// Each decoration is optional; the default style draws plain text. They
+/// are drawn in order background, shadow, outline, text.
+///
+/// # Example
+/// ```no_run
+/// use carose::TextAlign;
+/// use carose::windows::text::{TextBackground, TextShadow, TextStyle};
+/// # let mut window = carose::Window::new("Example", 800, 600);
+///
+/// window.show_text("score", "Score: 0", (10, 10), 3, 0xFFFFFF, TextAlign::Left);
+/// window.set_text_style("score", TextStyle {
+///     outline: Some(0x000000),
+///     shadow: Some(TextShadow { offset: (2, 2), color: 0x202020 }),
+///     background: Some(TextBackground { color: 0x000000, opacity: 128, padding: 4 }),
+/// });
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TextStyle {
+    /// Color of a 1 pixel outline around every glyph.
+    pub outline: Option<u32>,
+
+    /// Drop shadow behind the glyphs.
+    pub shadow: Option<TextShadow>,
+
+    /// Panel behind the whole text item.
+    pub background: Option<TextBackground>,
+}
+
+/// Character whose glyph is drawn in place of unsupported characters.
+pub const REPLACEMENT_CHAR: char = '\u{FFFD}';
+
+/// Returns the size in pixels of `content` drawn with the built-in font.
+///
+/// Each glyph is `5 * size` pixels square and glyphs are separated by a
+/// fixed 7 pixel gap. Empty text measures `(0, 0)`.
+pub fn measure_text(content: &str, size: usize) -> (usize, usize) {
+    Font::builtin().measure(content, size)
+}
+
+/// Returns the largest size, at most `size` and at least `1`, at which
+/// `content` is no wider than `available` pixels.
+fn fit_size(font: &Font, content: &str, size: usize, available: usize) -> usize {
+    let mut size = size.max(1);
+    while size > 1 && font.measure(content, size).0 > available {
+        size -= 1;
+    }
+    size
 }
 
 // A drawable text element rendered using the built-in bitmap font.
@@ -18,7 +107,8 @@ pub struct TextItem {
 
     /// The reference position of the text in window coordinates.
     ///
-    /// The meaning of this position depends on the selected alignment.
+    /// This is the top of the text; whether it is the left edge, center or
+    /// right edge depends on [`TextItem::align`].
     pub position: (usize, usize),
 
     /// Scaling factor applied to the 5x5 bitmap font.
@@ -30,7 +120,28 @@ pub struct TextItem {
     pub color: u32,
 
     /// Horizontal alignment mode used when rendering the text.
-    pub align: TextAlign, // new
+    pub align: TextAlign,
+
+    /// Render layer the text is drawn in.
+    ///
+    /// Defaults to [`Layer::HUD`].
+    pub layer: Layer,
+
+    /// Draw order within the layer; higher values are drawn on top.
+    pub z_index: i32,
+
+    /// Name of the font registered with [`Window::add_font`](super::Window::add_font).
+    ///
+    /// `None`, or a name that is not registered, uses the built-in font.
+    pub font: Option<String>,
+
+    /// Box the text is wrapped into, or `None` for a single line.
+    ///
+    /// See [`Window::show_text_box`](super::Window::show_text_box).
+    pub text_box: Option<TextBox>,
+
+    /// Outline, shadow and background decorations.
+    pub style: TextStyle,
 }
 
 impl super::Window {
@@ -54,7 +165,7 @@ impl super::Window {
         position: (usize, usize),
         size: usize,
         color: u32,
-        align: TextAlign, // new
+        align: TextAlign,
     ) {
         self.texts.insert(id.to_string(), TextItem {
             content: content.to_string(),
@@ -62,9 +173,43 @@ impl super::Window {
             size,
             color,
             align,
+            layer: Layer::HUD,
+            z_index: 0,
+            font: None,
+            text_box: None,
+            style: TextStyle::default(),
         });
     }
 
+    /// Displays a text element drawn with a registered font.
+    ///
+    /// This is [`Self::show_text`] followed by [`Self::set_text_font`];
+    /// if no font is registered under `font`, the built-in font is used.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use carose::TextAlign;
+    /// use carose::windows::font::Font;
+    /// # let mut window = carose::Window::new("Example", 800, 600);
+    ///
+    /// window.add_font("title", Font::load_bmfont("assets/title.fnt").unwrap());
+    /// window.show_text_with_font("title", "Carose", (400, 40), 1, 0xFFFFFF, TextAlign::Center, "title");
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn show_text_with_font(
+        &mut self,
+        id: &str,
+        content: &str,
+        position: (usize, usize),
+        size: usize,
+        color: u32,
+        align: TextAlign,
+        font: &str,
+    ) {
+        self.show_text(id, content, position, size, color, align);
+        self.set_text_font(id, Some(font));
+    }
+
     /// Updates the content of an existing text item.
     ///
     /// If no text item with the given `id` exists, this method does nothing.
@@ -78,6 +223,67 @@ impl super::Window {
         }
     }
 
+    /// Sets the render layer and z-index of a text item.
+    ///
+    /// Text items with the same layer and z-index are drawn in order of
+    /// their `id`. If no text item with the given `id` exists, this method
+    /// does nothing.
+    pub fn set_text_layer(&mut self, id: &str, layer: Layer, z_index: i32) {
+        if let Some(text_item) = self.texts.get_mut(id) {
+            text_item.layer = layer;
+            text_item.z_index = z_index;
+        }
+    }
+
+    /// Registers a font under a name so text items can use it.
+    ///
+    /// A font registered under an existing name replaces it.
+    pub fn add_font(&mut self, name: &str, font: Font) {
+        self.fonts.insert(name.to_string(), font);
+    }
+
+    /// Returns a registered font.
+    pub fn font(&self, name: &str) -> Option<&Font> {
+        self.fonts.get(name)
+    }
+
+    /// Sets the outline, shadow and background of a text item.
+    ///
+    /// If no text item with the given `id` exists, this method does nothing.
+    pub fn set_text_style(&mut self, id: &str, style: TextStyle) {
+        if let Some(text_item) = self.texts.get_mut(id) {
+            text_item.style = style;
+        }
+    }
+
+    /// Sets the font of a text item.
+    ///
+    /// `None` switches back to the built-in font. If no text item with the
+    /// given `id` exists, this method does nothing.
+    pub fn set_text_font(&mut self, id: &str, font: Option<&str>) {
+        if let Some(text_item) = self.texts.get_mut(id) {
+            text_item.font = font.map(str::to_string);
+        }
+    }
+
+    /// Returns the size in pixels of `content` drawn at `size` with the
+    /// built-in font.
+    ///
+    /// This matches exactly what [`Window::draw`](super::Window::draw)
+    /// renders, so it can be used to lay out text manually. Use
+    /// [`Font::measure`] for other fonts.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # let mut window = carose::Window::new("Example", 800, 600);
+    /// let (w, h) = window.measure_text("Game Over", 4);
+    /// let (width, height) = window.get_size();
+    /// window.show_text("over", "Game Over", ((width - w) / 2, (height - h) / 2), 4, 0xFFFFFF, carose::TextAlign::Left);
+    /// ```
+    pub fn measure_text(&self, content: &str, size: usize) -> (usize, usize) {
+        measure_text(content, size)
+    }
+
     /// Removes a text item from the window.
     ///
     /// After removal, the text will no longer be rendered.
@@ -88,6 +294,100 @@ impl super::Window {
 }
 
 
+/// Returns the font a text item is drawn with.
+pub(crate) fn resolve_font<'a>(item: &TextItem, fonts: &'a HashMap<String, Font>) -> &'a Font {
+    item.font
+        .as_ref()
+        .and_then(|name| fonts.get(name))
+        .unwrap_or(Font::builtin())
+}
+
+/// Resolves where and at what size a text item is drawn.
+///
+/// Returns the screen position of the top-left corner of the text and
+/// the glyph scale, after applying the view and the item's alignment.
+fn layout(item: &TextItem, font: &Font, view: View, frame_width: usize) -> (i32, i32, usize) {
+    let (ax, ay) = view.apply((item.position.0 as f32, item.position.1 as f32));
+    let mut size = item.size * view.zoom;
+
+    if item.align == TextAlign::AutoFit {
+        let available = (frame_width as i32 - ax).max(0) as usize;
+        size = fit_size(font, &item.content, size, available);
+    }
+
+    let width = font.measure(&item.content, size).0 as i32;
+    let x = match item.align {
+        TextAlign::Left | TextAlign::AutoFit => ax,
+        TextAlign::Center => ax - width / 2,
+        TextAlign::Right => ax - width,
+    };
+    (x, ay, size)
+}
+
+/// Draws a text item into the frame buffer.
+///
+/// The item's font is looked up in `fonts`, falling back to the built-in
+/// font. Glyph metrics are multiplied by the item's size times the view's
+/// zoom.
+pub(crate) fn draw_text(frame: &mut FrameBuffer, item: &TextItem, fonts: &HashMap<String, Font>, view: View) {
+    let font = resolve_font(item, fonts);
+    if let Some(text_box) = &item.text_box {
+        text_box::draw_text_box(frame, item, text_box, font, view);
+        return;
+    }
+
+    let (x, y, size) = layout(item, font, view, frame.size().0);
+    let (width, height) = font.measure(&item.content, size);
+    draw_background(frame, &item.style, (x, y, width, height));
+
+    let line: Vec<(char, u32)> = item.content.chars().map(|c| (c, item.color)).collect();
+    draw_styled_line(frame, font, &item.style, &line, x, y, size);
+}
+
+/// Draws the background panel of a style around a rectangle `(x, y, w, h)`.
+pub(crate) fn draw_background(frame: &mut FrameBuffer, style: &TextStyle, rect: (i32, i32, usize, usize)) {
+    if let Some(background) = style.background {
+        let (x, y, w, h) = rect;
+        let padding = background.padding;
+        frame.blend_rect(
+            x - padding as i32,
+            y - padding as i32,
+            w + 2 * padding,
+            h + 2 * padding,
+            background.color,
+            background.opacity,
+        );
+    }
+}
+
+/// Draws one line of colored characters with the shadow and outline of a style.
+pub(crate) fn draw_styled_line(
+    frame: &mut FrameBuffer,
+    font: &Font,
+    style: &TextStyle,
+    line: &[(char, u32)],
+    x: i32,
+    y: i32,
+    size: usize,
+) {
+    let recolor = |color: u32| line.iter().map(move |&(c, _)| (c, color));
+
+    if let Some(shadow) = style.shadow {
+        font.draw_line(frame, recolor(shadow.color), x + shadow.offset.0, y + shadow.offset.1, size);
+    }
+    if let Some(outline) = style.outline {
+        for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+            font.draw_line(frame, recolor(outline), x + dx, y + dy, size);
+        }
+    }
+    font.draw_line(frame, line.iter().copied(), x, y, size);
+}
+
+/// Returns the built-in 5x5 font.
+///
+/// Covers all printable ASCII characters, with distinct upper and lower
+/// case letters, plus a box glyph stored under [`REPLACEMENT_CHAR`].
+/// Each glyph is five rows of five cells, where `1` is a set pixel.
 pub fn get_font_map() -> HashMap<char, [[u8; 5]; 5]> {
     let mut map = HashMap::new();
 
@@ -413,5 +713,588 @@ pub fn get_font_map() -> HashMap<char, [[u8; 5]; 5]> {
         [0,0,1,0,0],
     ]);
 
+
+    // Letters a-z
+    map.insert('a', [
+        [0,0,0,0,0],
+        [0,1,1,1,0],
+        [1,0,0,1,0],
+        [1,0,0,1,0],
+        [0,1,1,0,1],
+    ]);
+
+    map.insert('b', [
+        [1,0,0,0,0],
+        [1,0,0,0,0],
+        [1,1,1,1,0],
+        [1,0,0,0,1],
+        [1,1,1,1,0],
+    ]);
+
+    map.insert('c', [
+        [0,0,0,0,0],
+        [0,1,1,1,1],
+        [1,0,0,0,0],
+        [1,0,0,0,0],
+        [0,1,1,1,1],
+    ]);
+
+    map.insert('d', [
+        [0,0,0,0,1],
+        [0,0,0,0,1],
+        [0,1,1,1,1],
+        [1,0,0,0,1],
+        [0,1,1,1,1],
+    ]);
+
+    map.insert('e', [
+        [0,0,0,0,0],
+        [0,1,1,1,0],
+        [1,1,1,1,1],
+        [1,0,0,0,0],
+        [0,1,1,1,1],
+    ]);
+
+    map.insert('f', [
+        [0,0,1,1,0],
+        [0,1,0,0,0],
+        [1,1,1,1,0],
+        [0,1,0,0,0],
+        [0,1,0,0,0],
+    ]);
+
+    map.insert('g', [
+        [0,1,1,1,1],
+        [1,0,0,0,1],
+        [0,1,1,1,1],
+        [0,0,0,0,1],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('h', [
+        [1,0,0,0,0],
+        [1,0,0,0,0],
+        [1,1,1,1,0],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+    ]);
+
+    map.insert('i', [
+        [0,0,1,0,0],
+        [0,0,0,0,0],
+        [0,1,1,0,0],
+        [0,0,1,0,0],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('j', [
+        [0,0,0,1,0],
+        [0,0,0,0,0],
+        [0,0,0,1,0],
+        [1,0,0,1,0],
+        [0,1,1,0,0],
+    ]);
+
+    map.insert('k', [
+        [1,0,0,0,0],
+        [1,0,0,1,0],
+        [1,1,1,0,0],
+        [1,0,0,1,0],
+        [1,0,0,0,1],
+    ]);
+
+    map.insert('l', [
+        [0,1,1,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('m', [
+        [0,0,0,0,0],
+        [1,1,0,1,0],
+        [1,0,1,0,1],
+        [1,0,1,0,1],
+        [1,0,0,0,1],
+    ]);
+
+    map.insert('n', [
+        [0,0,0,0,0],
+        [1,1,1,1,0],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+    ]);
+
+    map.insert('o', [
+        [0,0,0,0,0],
+        [0,1,1,1,0],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('p', [
+        [1,0,1,1,0],
+        [1,1,0,0,1],
+        [1,1,1,1,0],
+        [1,0,0,0,0],
+        [1,0,0,0,0],
+    ]);
+
+    map.insert('q', [
+        [0,1,1,1,1],
+        [1,0,0,0,1],
+        [0,1,1,1,1],
+        [0,0,0,0,1],
+        [0,0,0,0,1],
+    ]);
+
+    map.insert('r', [
+        [0,0,0,0,0],
+        [1,0,1,1,0],
+        [1,1,0,0,0],
+        [1,0,0,0,0],
+        [1,0,0,0,0],
+    ]);
+
+    map.insert('s', [
+        [0,0,0,0,0],
+        [0,1,1,1,1],
+        [0,1,1,0,0],
+        [0,0,0,1,1],
+        [1,1,1,1,0],
+    ]);
+
+    map.insert('t', [
+        [0,1,0,0,0],
+        [1,1,1,1,0],
+        [0,1,0,0,0],
+        [0,1,0,0,1],
+        [0,0,1,1,0],
+    ]);
+
+    map.insert('u', [
+        [0,0,0,0,0],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [0,1,1,1,1],
+    ]);
+
+    map.insert('v', [
+        [0,0,0,0,0],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [0,1,0,1,0],
+        [0,0,1,0,0],
+    ]);
+
+    map.insert('w', [
+        [0,0,0,0,0],
+        [1,0,0,0,1],
+        [1,0,1,0,1],
+        [1,0,1,0,1],
+        [0,1,0,1,0],
+    ]);
+
+    map.insert('x', [
+        [0,0,0,0,0],
+        [1,0,0,1,0],
+        [0,1,1,0,0],
+        [0,1,1,0,0],
+        [1,0,0,1,0],
+    ]);
+
+    map.insert('y', [
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [0,1,1,1,1],
+        [0,0,0,0,1],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('z', [
+        [0,0,0,0,0],
+        [1,1,1,1,1],
+        [0,0,0,1,0],
+        [0,1,0,0,0],
+        [1,1,1,1,1],
+    ]);
+
+    // Remaining printable ASCII
+    map.insert('"', [
+        [0,1,0,1,0],
+        [0,1,0,1,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('#', [
+        [0,1,0,1,0],
+        [1,1,1,1,1],
+        [0,1,0,1,0],
+        [1,1,1,1,1],
+        [0,1,0,1,0],
+    ]);
+
+    map.insert('$', [
+        [0,1,1,1,1],
+        [1,0,1,0,0],
+        [0,1,1,1,0],
+        [0,0,1,0,1],
+        [1,1,1,1,0],
+    ]);
+
+    map.insert('%', [
+        [1,1,0,0,1],
+        [1,1,0,1,0],
+        [0,0,1,0,0],
+        [0,1,0,1,1],
+        [1,0,0,1,1],
+    ]);
+
+    map.insert('&', [
+        [0,1,1,0,0],
+        [1,0,0,1,0],
+        [0,1,1,0,1],
+        [1,0,0,1,0],
+        [0,1,1,0,1],
+    ]);
+
+    map.insert('\'', [
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('(', [
+        [0,0,0,1,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,0,1,0],
+    ]);
+
+    map.insert(')', [
+        [0,1,0,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,1,0,0,0],
+    ]);
+
+    map.insert('*', [
+        [0,0,0,0,0],
+        [1,0,1,0,1],
+        [0,1,1,1,0],
+        [1,0,1,0,1],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('+', [
+        [0,0,0,0,0],
+        [0,0,1,0,0],
+        [1,1,1,1,1],
+        [0,0,1,0,0],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('-', [
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+        [0,1,1,1,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('/', [
+        [0,0,0,0,1],
+        [0,0,0,1,0],
+        [0,0,1,0,0],
+        [0,1,0,0,0],
+        [1,0,0,0,0],
+    ]);
+
+    map.insert(':', [
+        [0,0,0,0,0],
+        [0,0,1,0,0],
+        [0,0,0,0,0],
+        [0,0,1,0,0],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert(';', [
+        [0,0,0,0,0],
+        [0,0,1,0,0],
+        [0,0,0,0,0],
+        [0,0,1,0,0],
+        [0,1,0,0,0],
+    ]);
+
+    map.insert('<', [
+        [0,0,0,1,0],
+        [0,0,1,0,0],
+        [0,1,0,0,0],
+        [0,0,1,0,0],
+        [0,0,0,1,0],
+    ]);
+
+    map.insert('=', [
+        [0,0,0,0,0],
+        [1,1,1,1,1],
+        [0,0,0,0,0],
+        [1,1,1,1,1],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('>', [
+        [0,1,0,0,0],
+        [0,0,1,0,0],
+        [0,0,0,1,0],
+        [0,0,1,0,0],
+        [0,1,0,0,0],
+    ]);
+
+    map.insert('@', [
+        [0,1,1,1,0],
+        [1,0,0,0,1],
+        [1,0,1,1,1],
+        [1,0,0,0,0],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('[', [
+        [0,1,1,1,0],
+        [0,1,0,0,0],
+        [0,1,0,0,0],
+        [0,1,0,0,0],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('\\', [
+        [1,0,0,0,0],
+        [0,1,0,0,0],
+        [0,0,1,0,0],
+        [0,0,0,1,0],
+        [0,0,0,0,1],
+    ]);
+
+    map.insert(']', [
+        [0,1,1,1,0],
+        [0,0,0,1,0],
+        [0,0,0,1,0],
+        [0,0,0,1,0],
+        [0,1,1,1,0],
+    ]);
+
+    map.insert('^', [
+        [0,0,1,0,0],
+        [0,1,0,1,0],
+        [1,0,0,0,1],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('_', [
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+        [1,1,1,1,1],
+    ]);
+
+    map.insert('`', [
+        [0,1,0,0,0],
+        [0,0,1,0,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+        [0,0,0,0,0],
+    ]);
+
+    map.insert('{', [
+        [0,0,1,1,0],
+        [0,0,1,0,0],
+        [0,1,0,0,0],
+        [0,0,1,0,0],
+        [0,0,1,1,0],
+    ]);
+
+    map.insert('|', [
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+        [0,0,1,0,0],
+    ]);
+
+    map.insert('}', [
+        [0,1,1,0,0],
+        [0,0,1,0,0],
+        [0,0,0,1,0],
+        [0,0,1,0,0],
+        [0,1,1,0,0],
+    ]);
+
+    map.insert('~', [
+        [0,0,0,0,0],
+        [0,1,0,0,0],
+        [1,0,1,0,1],
+        [0,0,0,1,0],
+        [0,0,0,0,0],
+    ]);
+
+    // Replacement glyph for unsupported characters
+    map.insert(REPLACEMENT_CHAR, [
+        [1,1,1,1,1],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [1,0,0,0,1],
+        [1,1,1,1,1],
+    ]);
+
     map
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowercase_glyphs_differ_from_uppercase() {
+        let map = get_font_map();
+        for lower in 'a'..='z' {
+            let upper = lower.to_ascii_uppercase();
+            assert_ne!(map[&lower], map[&upper], "'{}' is drawn the same as '{}'", lower, upper);
+        }
+    }
+
+    fn item(content: &str, position: (usize, usize), size: usize, align: TextAlign) -> TextItem {
+        TextItem {
+            content: content.to_string(),
+            position,
+            size,
+            color: 0xFFFFFF,
+            align,
+            layer: Layer::HUD,
+            z_index: 0,
+            font: None,
+            text_box: None,
+            style: TextStyle::default(),
+        }
+    }
+
+    #[test]
+    fn measure_scales_glyphs_but_not_the_gap() {
+        assert_eq!(measure_text("", 3), (0, 0));
+        assert_eq!(measure_text("A", 1), (5, 5));
+        assert_eq!(measure_text("AB", 1), (17, 5));
+        assert_eq!(measure_text("AB", 2), (27, 10));
+        assert_eq!(measure_text("abc", 3), (59, 15));
+    }
+
+    #[test]
+    fn alignment_anchors_the_measured_width() {
+        let font = Font::builtin();
+        let at = |align| layout(&item("AB", (50, 7), 1, align), font, View::SCREEN, 100);
+        assert_eq!(at(TextAlign::Left), (50, 7, 1));
+        assert_eq!(at(TextAlign::Center), (42, 7, 1));
+        assert_eq!(at(TextAlign::Right), (33, 7, 1));
+
+        let zoomed = View { origin: (10.0, 0.0), zoom: 2 };
+        assert_eq!(layout(&item("AB", (50, 7), 1, TextAlign::Right), font, zoomed, 200), (83, 14, 2));
+    }
+
+    #[test]
+    fn auto_fit_shrinks_until_the_text_fits() {
+        let font = Font::builtin();
+        // "HELLO" is 25 * size + 28 pixels wide.
+        let fit = |x| layout(&item("HELLO", (x, 0), 4, TextAlign::AutoFit), font, View::SCREEN, 100);
+        assert_eq!(fit(0), (0, 0, 2));
+        assert_eq!(fit(22), (22, 0, 2));
+        assert_eq!(fit(23), (23, 0, 1));
+        assert_eq!(fit(90), (90, 0, 1));
+        assert_eq!(layout(&item("HI", (0, 0), 4, TextAlign::AutoFit), font, View::SCREEN, 100).2, 4);
+    }
+
+    #[test]
+    fn centered_text_is_drawn_where_it_is_laid_out() {
+        let mut window = crate::Window::headless(100, 20);
+        window.show_text("t", "AB", (50, 5), 1, 0xFFFFFF, TextAlign::Center);
+        window.draw();
+
+        // The middle row of 'A' spans its whole cell, columns 42..47.
+        let row: Vec<u32> = (40..49).map(|x| window.frame_pixel(x, 7).unwrap()).collect();
+        assert_eq!(row, [0, 0, 0xFFFFFF, 0xFFFFFF, 0xFFFFFF, 0xFFFFFF, 0xFFFFFF, 0, 0]);
+    }
+
+    /// Draws a white 'A' at (10, 10) with `style` and returns the frame.
+    ///
+    /// The top row of 'A' is lit in columns 11..14 only.
+    fn styled_a(style: TextStyle) -> crate::Window {
+        let mut window = crate::Window::headless(30, 30);
+        window.show_text("a", "A", (10, 10), 1, 0xFFFFFF, TextAlign::Left);
+        window.set_text_style("a", style);
+        window.draw();
+        window
+    }
+
+    #[test]
+    fn outline_surrounds_every_glyph_pixel() {
+        let window = styled_a(TextStyle { outline: Some(0xFF0000), ..TextStyle::default() });
+        let pixel = |x, y| window.frame_pixel(x, y).unwrap();
+
+        assert_eq!(pixel(11, 10), 0xFFFFFF);
+        assert_eq!(pixel(10, 10), 0xFF0000);
+        assert_eq!(pixel(10, 9), 0xFF0000);
+        assert_eq!(pixel(14, 9), 0xFF0000);
+        assert_eq!(pixel(15, 11), 0xFF0000);
+        assert_eq!(pixel(12, 11), 0xFF0000);
+        assert_eq!(pixel(9, 9), 0);
+        assert_eq!(pixel(16, 12), 0);
+        assert_eq!(pixel(10, 15), 0xFF0000);
+        assert_eq!(pixel(10, 16), 0);
+    }
+
+    #[test]
+    fn shadow_is_drawn_at_its_offset_under_the_text() {
+        let shadow = TextShadow { offset: (1, 6), color: 0x00FF00 };
+        let window = styled_a(TextStyle { shadow: Some(shadow), ..TextStyle::default() });
+        let pixel = |x, y| window.frame_pixel(x, y).unwrap();
+
+        assert_eq!(pixel(11, 10), 0xFFFFFF);
+        assert_eq!(pixel(12, 16), 0x00FF00);
+        assert_eq!(pixel(11, 16), 0);
+        assert_eq!(pixel(15, 16), 0);
+
+        // Overlapping pixels show the text.
+        let shadow = TextShadow { offset: (1, 0), color: 0x00FF00 };
+        let window = styled_a(TextStyle { shadow: Some(shadow), ..TextStyle::default() });
+        assert_eq!(window.frame_pixel(12, 10), Some(0xFFFFFF));
+        assert_eq!(window.frame_pixel(14, 10), Some(0x00FF00));
+    }
+
+    #[test]
+    fn background_covers_the_text_and_its_padding() {
+        let background = TextBackground { color: 0x0000FF, opacity: 255, padding: 2 };
+        let window = styled_a(TextStyle { background: Some(background), ..TextStyle::default() });
+        let pixel = |x, y| window.frame_pixel(x, y).unwrap();
+
+        assert_eq!(pixel(11, 10), 0xFFFFFF);
+        assert_eq!(pixel(10, 10), 0x0000FF);
+        for (x, y) in [(8, 8), (16, 8), (8, 16), (16, 16)] {
+            assert_eq!(pixel(x, y), 0x0000FF, "({}, {})", x, y);
+        }
+        for (x, y) in [(7, 8), (8, 7), (17, 16), (16, 17)] {
+            assert_eq!(pixel(x, y), 0, "({}, {})", x, y);
+        }
+
+        let half = TextBackground { opacity: 128, ..background };
+        let window = styled_a(TextStyle { background: Some(half), ..TextStyle::default() });
+        assert_eq!(window.frame_pixel(8, 8), Some(0x000080));
+    }
+}
@@ -0,0 +1,477 @@
+use super::camera::View;
+use super::font::Font;
+use super::render::FrameBuffer;
+use super::text::{draw_background, draw_styled_line, resolve_font, TextAlign, TextItem, TextStyle};
+use super::Layer;
+
+/// A character together with the color it is drawn in.
+type StyledChar = (char, u32);
+
+/// What a text box does with lines that do not fit its height.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Overflow {
+    /// Lines below the box are cut off at its bottom edge.
+    Clip,
+
+    /// Only the lines starting at [`TextBox::scroll`] are shown.
+    Scroll,
+
+    /// The last visible line ends in `...` if more text follows.
+    Ellipsis,
+}
+
+/// Bounding rectangle and layout rules for multi-line text.
+///
+/// A text item with a text box wraps its content at word boundaries to
+/// the box width, starts a new line at every `\n`, and never draws
+/// outside the box. Words longer than a line are broken between
+/// characters.
+///
+/// The content may contain color markup:
+/// `[color=#rrggbb]...[/color]`. Color tags can be nested; anything
+/// else in square brackets is drawn as written.
+///
+/// The item's alignment applies to each line within the box;
+/// [`TextAlign::AutoFit`] behaves like [`TextAlign::Left`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextBox {
+    /// Width of the box in pixels.
+    pub width: usize,
+
+    /// Height of the box in pixels.
+    pub height: usize,
+
+    /// Extra space between lines in pixels.
+    pub line_spacing: usize,
+
+    /// How lines that do not fit are handled.
+    pub overflow: Overflow,
+
+    /// Index of the first visible line when [`TextBox::overflow`] is
+    /// [`Overflow::Scroll`].
+    pub scroll: usize,
+
+    /// Number of characters drawn, for typewriter effects, or `None` to
+    /// draw all of them.
+    ///
+    /// The text is wrapped as if it were fully shown, so words do not jump
+    /// between lines as they are revealed. Markup does not count, but
+    /// spaces and `\n` dropped at line breaks do, so the count runs over
+    /// the same characters as [`visible_chars`].
+    pub reveal: Option<usize>,
+}
+
+impl TextBox {
+    /// Creates a clipping text box of the given size with 4 pixels between lines.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            line_spacing: 4,
+            overflow: Overflow::Clip,
+            scroll: 0,
+            reveal: None,
+        }
+    }
+}
+
+impl super::Window {
+    /// Displays a multi-line text element inside a box.
+    ///
+    /// If a text item with the same `id` already exists, it will be
+    /// replaced. The text is left-aligned within the box; set
+    /// [`TextItem::align`] to center or right-align each line.
+    ///
+    /// # Parameters
+    /// - `id`: Unique identifier for this text item.
+    /// - `content`: Text to display, which may contain `\n` and color markup.
+    /// - `position`: Top-left corner of the box.
+    /// - `size`: Scaling factor for the font.
+    /// - `color`: Default text color in 0xRRGGBB format.
+    /// - `text_box`: Size and layout rules of the box.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use carose::windows::text_box::{Overflow, TextBox};
+    /// # let mut window = carose::Window::new("Example", 800, 600);
+    ///
+    /// let mut text_box = TextBox::new(300, 80);
+    /// text_box.overflow = Overflow::Ellipsis;
+    /// window.show_text_box(
+    ///     "hint",
+    ///     "Press [color=#ffff00]Space[/color] to jump.\nAvoid the pipes!",
+    ///     (20, 20),
+    ///     2,
+    ///     0xFFFFFF,
+    ///     text_box,
+    /// );
+    /// ```
+    pub fn show_text_box(
+        &mut self,
+        id: &str,
+        content: &str,
+        position: (usize, usize),
+        size: usize,
+        color: u32,
+        text_box: TextBox,
+    ) {
+        self.texts.insert(id.to_string(), TextItem {
+            content: content.to_string(),
+            position,
+            size,
+            color,
+            align: TextAlign::Left,
+            layer: Layer::HUD,
+            z_index: 0,
+            font: None,
+            text_box: Some(text_box),
+            style: TextStyle::default(),
+        });
+    }
+
+    /// Returns the number of lines a text box item wraps into.
+    ///
+    /// Returns `None` if no text item with the given `id` exists or it has
+    /// no text box.
+    pub fn text_line_count(&self, id: &str) -> Option<usize> {
+        let item = self.texts.get(id)?;
+        let text_box = item.text_box.as_ref()?;
+        let font = resolve_font(item, &self.fonts);
+        Some(layout_lines(item, font, item.size, text_box.width).len())
+    }
+
+    /// Scrolls a text box item by a number of lines.
+    ///
+    /// Positive values scroll down. The scroll position stays between the
+    /// first line and the point where the last line reaches the bottom of
+    /// the box. This only has a visible effect with [`Overflow::Scroll`].
+    /// If no text box item with the given `id` exists, this method does
+    /// nothing.
+    pub fn scroll_text(&mut self, id: &str, lines: isize) {
+        let Some(item) = self.texts.get(id) else { return };
+        let Some(text_box) = item.text_box else { return };
+        let font = resolve_font(item, &self.fonts);
+        let total = layout_lines(item, font, item.size, text_box.width).len();
+        let visible = visible_lines(font, item.size, &text_box, 1);
+        let max_scroll = total.saturating_sub(visible);
+
+        if let Some(text_box) = self.texts.get_mut(id).and_then(|item| item.text_box.as_mut()) {
+            text_box.scroll = text_box.scroll.saturating_add_signed(lines).min(max_scroll);
+        }
+    }
+}
+
+/// Splits `content` into characters colored according to its markup.
+fn parse_markup(content: &str, color: u32) -> Vec<StyledChar> {
+    let mut chars = Vec::with_capacity(content.len());
+    let mut colors = vec![color];
+    let mut rest = content;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            if let Some(after) = rest.strip_prefix("[/color]") {
+                if colors.len() > 1 {
+                    colors.pop();
+                }
+                rest = after;
+                continue;
+            }
+            if let Some((value, after)) = rest
+                .strip_prefix("[color=#")
+                .and_then(|tag| tag.split_once(']'))
+                && value.len() == 6
+                && value.bytes().all(|b| b.is_ascii_hexdigit())
+                && let Ok(value) = u32::from_str_radix(value, 16)
+            {
+                colors.push(value);
+                rest = after;
+                continue;
+            }
+        }
+
+        chars.push((c, *colors.last().unwrap_or(&color)));
+        rest = &rest[c.len_utf8()..];
+    }
+
+    chars
+}
+
+/// Returns the characters of `content` without its markup.
+pub(crate) fn visible_chars(content: &str) -> Vec<char> {
+    parse_markup(content, 0).into_iter().map(|(c, _)| c).collect()
+}
+
+/// Wraps colored text into lines no wider than `width` pixels.
+fn wrap(chars: &[StyledChar], font: &Font, size: usize, width: usize) -> Vec<Vec<StyledChar>> {
+    let mut lines = Vec::new();
+
+    for paragraph in chars.split(|&(c, _)| c == '\n') {
+        let mut line: Vec<StyledChar> = Vec::new();
+
+        for word in paragraph.split(|&(c, _)| c == ' ') {
+            let mut candidate = line.clone();
+            if let Some(&(_, color)) = line.last() {
+                candidate.push((' ', color));
+            }
+            candidate.extend_from_slice(word);
+
+            if font.line_width(&candidate, size) <= width {
+                line = candidate;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(line);
+            }
+            line = word.to_vec();
+
+            // Break words that are too long for a line on their own.
+            while line.len() > 1 && font.line_width(&line, size) > width {
+                let mut split = line.len() - 1;
+                while split > 1 && font.line_width(&line[..split], size) > width {
+                    split -= 1;
+                }
+                lines.push(line[..split].to_vec());
+                line.drain(..split);
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Cuts wrapped lines down to the characters among the first `reveal`
+/// characters of `chars`, the unwrapped text they were wrapped from.
+///
+/// Lines hold the characters of `chars` in order, minus the spaces and
+/// line breaks dropped by [`wrap`], so each line character is matched to
+/// the next equal character of the revealed text.
+fn reveal_lines(lines: &mut [Vec<StyledChar>], chars: &[StyledChar], reveal: usize) {
+    let mut revealed = chars[..reveal.min(chars.len())].iter();
+    for line in lines {
+        let shown = line
+            .iter()
+            .take_while(|&&(c, _)| revealed.any(|&(r, _)| r == c))
+            .count();
+        line.truncate(shown);
+    }
+}
+
+/// Parses and wraps the content of a text item.
+fn layout_lines(item: &TextItem, font: &Font, size: usize, width: usize) -> Vec<Vec<StyledChar>> {
+    wrap(&parse_markup(&item.content, item.color), font, size, width)
+}
+
+/// Returns how many whole lines fit in a text box.
+fn visible_lines(font: &Font, size: usize, text_box: &TextBox, zoom: usize) -> usize {
+    let step = font.line_height * size + text_box.line_spacing * zoom;
+    ((text_box.height * zoom + text_box.line_spacing * zoom) / step.max(1)).max(1)
+}
+
+/// Shortens a line so that it ends in `...` and fits `width` pixels.
+fn ellipsize(mut line: Vec<StyledChar>, font: &Font, size: usize, width: usize) -> Vec<StyledChar> {
+    let color = line.last().map_or(0, |&(_, color)| color);
+    let dots = [('.', color); 3];
+
+    loop {
+        while line.last().is_some_and(|&(c, _)| c == ' ') {
+            line.pop();
+        }
+        let mut candidate = line.clone();
+        candidate.extend_from_slice(&dots);
+        if line.is_empty() || font.line_width(&candidate, size) <= width {
+            return candidate;
+        }
+        line.pop();
+    }
+}
+
+/// Draws a text item that has a text box.
+pub(crate) fn draw_text_box(
+    frame: &mut FrameBuffer,
+    item: &TextItem,
+    text_box: &TextBox,
+    font: &Font,
+    view: View,
+) {
+    let (x, y) = view.apply((item.position.0 as f32, item.position.1 as f32));
+    let size = item.size * view.zoom;
+    let (width, height) = (text_box.width * view.zoom, text_box.height * view.zoom);
+    let step = (font.line_height * size + text_box.line_spacing * view.zoom) as i32;
+
+    let chars = parse_markup(&item.content, item.color);
+    let mut lines = wrap(&chars, font, size, width);
+    let visible = visible_lines(font, size, text_box, view.zoom);
+
+    if let Some(reveal) = text_box.reveal {
+        reveal_lines(&mut lines, &chars, reveal);
+    }
+
+    match text_box.overflow {
+        Overflow::Clip => {}
+        Overflow::Scroll => {
+            let first = text_box.scroll.min(lines.len().saturating_sub(visible));
+            lines.drain(..first);
+            lines.truncate(visible);
+        }
+        Overflow::Ellipsis => {
+            if lines.len() > visible {
+                lines.truncate(visible);
+                if let Some(last) = lines.pop() {
+                    lines.push(ellipsize(last, font, size, width));
+                }
+            }
+        }
+    }
+
+    draw_background(frame, &item.style, (x, y, width, height));
+    frame.set_clip(Some((x, y, width, height)));
+    for (i, line) in lines.into_iter().enumerate() {
+        let line_width = font.line_width(&line, size) as i32;
+        let offset = match item.align {
+            TextAlign::Left | TextAlign::AutoFit => 0,
+            TextAlign::Center => (width as i32 - line_width) / 2,
+            TextAlign::Right => width as i32 - line_width,
+        };
+        draw_styled_line(frame, font, &item.style, &line, x + offset, y + i as i32 * step, size);
+    }
+    frame.set_clip(None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> Vec<StyledChar> {
+        text.chars().map(|c| (c, 0)).collect()
+    }
+
+    fn text_of(lines: &[Vec<StyledChar>]) -> Vec<String> {
+        lines.iter().map(|line| line.iter().map(|&(c, _)| c).collect()).collect()
+    }
+
+    #[test]
+    fn reveal_counts_characters_dropped_by_wrapping() {
+        let font = Font::builtin();
+        let chars = plain("ab cd\nefghij");
+        // Room for three built-in glyphs per line.
+        let lines = wrap(&chars, font, 1, 3 * 5 + 2 * 7);
+        assert_eq!(text_of(&lines), ["ab", "cd", "efg", "hij"]);
+
+        let reveal = |count: usize| {
+            let mut lines = lines.clone();
+            reveal_lines(&mut lines, &chars, count);
+            text_of(&lines)
+        };
+        assert_eq!(reveal(0), ["", "", "", ""]);
+        assert_eq!(reveal(2), ["ab", "", "", ""]);
+        assert_eq!(reveal(3), ["ab", "", "", ""]);
+        assert_eq!(reveal(4), ["ab", "c", "", ""]);
+        assert_eq!(reveal(6), ["ab", "cd", "", ""]);
+        assert_eq!(reveal(9), ["ab", "cd", "efg", ""]);
+        assert_eq!(reveal(11), ["ab", "cd", "efg", "hi"]);
+        assert_eq!(reveal(chars.len()), text_of(&lines));
+        assert_eq!(visible_chars("ab cd\nefghij").len(), chars.len());
+    }
+
+    /// Width in pixels of `glyphs` built-in glyphs at size 1.
+    fn glyphs(glyphs: usize) -> usize {
+        glyphs * 5 + (glyphs - 1) * 7
+    }
+
+    #[test]
+    fn wrap_breaks_at_spaces_and_newlines() {
+        let font = Font::builtin();
+        let lines = wrap(&plain("ab cd ef"), font, 1, glyphs(5));
+        assert_eq!(text_of(&lines), ["ab cd", "ef"]);
+
+        let lines = wrap(&plain("ab cd ef"), font, 1, glyphs(4));
+        assert_eq!(text_of(&lines), ["ab", "cd", "ef"]);
+
+        let lines = wrap(&plain("a\n\nb c"), font, 1, glyphs(3));
+        assert_eq!(text_of(&lines), ["a", "", "b c"]);
+
+        // Size scales glyphs, so fewer fit.
+        let lines = wrap(&plain("ab cd"), font, 2, glyphs(5));
+        assert_eq!(text_of(&lines), ["ab", "cd"]);
+    }
+
+    #[test]
+    fn wrap_breaks_long_words_between_characters() {
+        let font = Font::builtin();
+        let lines = wrap(&plain("x abcdefg y"), font, 1, glyphs(3));
+        assert_eq!(text_of(&lines), ["x", "abc", "def", "g y"]);
+
+        // A single glyph wider than the box still gets a line of its own.
+        let lines = wrap(&plain("ab"), font, 1, 3);
+        assert_eq!(text_of(&lines), ["a", "b"]);
+    }
+
+    #[test]
+    fn markup_colors_nest_and_unknown_tags_are_text() {
+        let chars = parse_markup("a[color=#ff0000]b[color=#00FF00]c[/color]d[/color]e[/color]f", 0xFFFFFF);
+        assert_eq!(
+            chars,
+            [('a', 0xFFFFFF), ('b', 0xFF0000), ('c', 0x00FF00), ('d', 0xFF0000), ('e', 0xFFFFFF), ('f', 0xFFFFFF)]
+        );
+
+        for literal in ["[b]x", "[color=#12345]x", "[color=#+12345]x", "[color=#-12345]x", "[color=#gggggg]x", "[color=#ff0000"] {
+            assert_eq!(visible_chars(literal).iter().collect::<String>(), literal);
+        }
+    }
+
+    /// Draws `content` in a 60x`height` box at size 1 with 4 pixels
+    /// between lines, so a line starts every 9 pixels and 5 glyphs fit.
+    fn render_box(content: &str, height: usize, overflow: Overflow, scroll: usize) -> Vec<u32> {
+        let mut window = crate::Window::headless(60, 30);
+        let mut text_box = TextBox::new(60, height);
+        text_box.overflow = overflow;
+        text_box.scroll = scroll;
+        window.show_text_box("box", content, (0, 0), 1, 0xFFFFFF, text_box);
+        window.draw();
+        window.frame().to_vec()
+    }
+
+    fn lit_rows(frame: &[u32]) -> Vec<usize> {
+        (0..30).filter(|&y| frame[y * 60..(y + 1) * 60].iter().any(|&p| p != 0)).collect()
+    }
+
+    #[test]
+    fn clip_cuts_lines_at_the_box_edge() {
+        let full = render_box("A\nB\nC", 30, Overflow::Clip, 0);
+        assert_eq!(lit_rows(&full), [0, 1, 2, 3, 4, 9, 10, 11, 12, 13, 18, 19, 20, 21, 22]);
+
+        let clipped = render_box("A\nB\nC", 20, Overflow::Clip, 0);
+        assert_eq!(lit_rows(&clipped), [0, 1, 2, 3, 4, 9, 10, 11, 12, 13, 18, 19]);
+        assert_eq!(clipped[..20 * 60], full[..20 * 60]);
+    }
+
+    #[test]
+    fn scroll_shows_lines_from_the_scroll_position() {
+        // 14 pixels fit two lines.
+        assert_eq!(render_box("A\nB\nC", 14, Overflow::Scroll, 1), render_box("B\nC", 14, Overflow::Clip, 0));
+        assert_eq!(render_box("A\nB\nC", 14, Overflow::Scroll, 9), render_box("B\nC", 14, Overflow::Clip, 0));
+
+        let mut window = crate::Window::headless(40, 30);
+        let mut text_box = TextBox::new(40, 14);
+        text_box.overflow = Overflow::Scroll;
+        window.show_text_box("box", "A\nB\nC", (0, 0), 1, 0xFFFFFF, text_box);
+        assert_eq!(window.text_line_count("box"), Some(3));
+        let scroll = |window: &crate::Window| window.texts["box"].text_box.unwrap().scroll;
+        window.scroll_text("box", 5);
+        assert_eq!(scroll(&window), 1);
+        window.scroll_text("box", -5);
+        assert_eq!(scroll(&window), 0);
+    }
+
+    #[test]
+    fn ellipsis_marks_the_last_visible_line() {
+        assert_eq!(render_box("A\nB\nC", 14, Overflow::Ellipsis, 0), render_box("A\nB...", 14, Overflow::Clip, 0));
+        assert_eq!(render_box("A\nB", 14, Overflow::Ellipsis, 0), render_box("A\nB", 14, Overflow::Clip, 0));
+
+        // The dots replace characters when the line is full.
+        assert_eq!(render_box("A\nBCD\nE", 14, Overflow::Ellipsis, 0), render_box("A\nBC...", 14, Overflow::Clip, 0));
+    }
+}
@@ -0,0 +1,27 @@
+use carose::sprites::{Sprite, SpriteType};
+use carose::testing::{assert_snapshot, render_frames};
+use carose::{TextAlign, Window};
+
+/// Path of a checked-in reference image.
+fn reference(name: &str) -> String {
+    format!("{}/tests/snapshots/{}.png", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn text_and_clipped_sprites() {
+    let mut window = Window::headless(96, 56);
+    window.set_background_color(0x202040);
+    window.show_text("title", "Hi, ok?", (48, 4), 1, 0xFFFFFF, TextAlign::Center);
+    window.show_text("edge", "EDGE", (84, 40), 2, 0xFFD000, TextAlign::Left);
+
+    // Hangs off the left and bottom edges.
+    window.add_sprite(Sprite::new_color((-6.0, 46.0), (16, 16), SpriteType::Enemy, 1, 0xE03030, false));
+
+    // Hangs off the top and right edges, with a transparent corner.
+    let mut pixels = vec![vec![0xFF30C060u32; 12]; 12];
+    pixels[11][0] = 0x00000000;
+    window.add_sprite(Sprite::new_bitmap((88.0, -4.0), SpriteType::Player, 1, pixels, false));
+
+    render_frames(&mut window, 2, |_, _| {});
+    assert_snapshot(&window, reference("text_and_clipped_sprites"), 0);
+}